@@ -1,15 +1,152 @@
-use rusqlite::Connection;
-use tauri::{App, Manager};
+use rusqlite::{Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::{App, AppHandle, Manager};
 use chrono;
-use crate::models::{Platform, Game};
+use crate::encryption::database_key;
+use crate::error::AppError;
+use crate::models::{Platform, Game, GameExecutable, GameFilter, GameRelations, GenreWithCount, PlatformSort, SourceGame, DatabaseMaintenance, LibraryStats, OrphanedRow, PlaytimeEntry, PlaytimeReport};
+use crate::paths::{display_cover_path, resolve_path};
+
+const DB_FILE_NAME: &str = "app.db";
+const BUSY_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(5000);
+
+pub fn get_db_path(app: &AppHandle) -> Result<PathBuf, tauri::Error> {
+    Ok(app.path().app_data_dir()?.join(DB_FILE_NAME))
+}
+
+// Covers imported from local files are copied here so they outlive the original
+pub fn get_covers_dir(app: &AppHandle) -> Result<PathBuf, tauri::Error> {
+    Ok(app.path().app_data_dir()?.join("covers"))
+}
+
+pub fn get_backups_dir(app: &AppHandle) -> Result<PathBuf, tauri::Error> {
+    Ok(app.path().app_data_dir()?.join("backups"))
+}
+
+pub const EXTENSIONS_DIR_KEY: &str = "extensions_dir";
+
+// Extensions live under app_data_dir unless the `extensions_dir` setting points elsewhere.
+// Must run after init_database so the settings table exists.
+pub fn resolve_extensions_dir(app: &App) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let data_dir = app.path().app_data_dir()?;
+    let conn = open_connection(&data_dir.join(DB_FILE_NAME))?;
+    let extensions_dir = match get_setting_value(&conn, EXTENSIONS_DIR_KEY)? {
+        Some(dir) if !dir.trim().is_empty() => PathBuf::from(dir),
+        _ => data_dir.join("extensions"),
+    };
+    std::fs::create_dir_all(&extensions_dir)?;
+    Ok(extensions_dir)
+}
+
+// SQLite only enforces foreign keys (and ON DELETE CASCADE) when the pragma is
+// set on each connection, so every connection must be opened through here.
+// It is also where the SQLCipher key is applied once the database is encrypted.
+pub fn open_connection(db_path: &Path) -> Result<Connection, rusqlite::Error> {
+    let key = database_key(db_path).map_err(|e| {
+        rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CANTOPEN), Some(e.to_string()))
+    })?;
+    let conn = Connection::open(db_path)?;
+    if let Some(key) = key {
+        conn.pragma_update(None, "key", key)?;
+    }
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    // Commands each open their own connection, so a writer waits for another's lock
+    // (e.g. a scan inserting games) instead of failing straight away with SQLITE_BUSY
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    Ok(conn)
+}
+
+// Takes the write lock up front. A deferred transaction that reads before writing can't wait on
+// busy_timeout when another writer got in first; WAL fails it straight away with SQLITE_BUSY.
+pub(crate) fn write_transaction(conn: &Connection) -> Result<rusqlite::Transaction<'_>, rusqlite::Error> {
+    rusqlite::Transaction::new_unchecked(conn, rusqlite::TransactionBehavior::Immediate)
+}
+
+// Connection helper for Tauri commands, which report errors as strings
+pub fn get_connection(app: &AppHandle) -> Result<Connection, String> {
+    let db_path = get_db_path(app).map_err(|e| e.to_string())?;
+    open_connection(&db_path).map_err(|e| e.to_string())
+}
+
+// Main file plus WAL, which is where reclaimable space tends to pile up
+fn database_file_size(db_path: &Path) -> u64 {
+    let wal_path = PathBuf::from(format!("{}-wal", db_path.display()));
+    [db_path.to_path_buf(), wal_path].iter().filter_map(|path| std::fs::metadata(path).ok()).map(|meta| meta.len()).sum()
+}
+
+/// Checkpoints the WAL and vacuums the database, returning its size before and after.
+/// VACUUM can't run inside a transaction, so this refuses if one is open.
+pub fn compact_database(conn: &Connection, db_path: &Path) -> Result<DatabaseMaintenance, AppError> {
+    if !conn.is_autocommit() {
+        return Err(AppError::Validation("Database maintenance cannot run inside a transaction".to_string()));
+    }
+    let size_before = database_file_size(db_path);
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+    conn.execute_batch("VACUUM;")?;
+    Ok(DatabaseMaintenance { size_before, size_after: database_file_size(db_path) })
+}
+
+/// Copies the database file into `backups_dir` as `app-<timestamp>.db` after checkpointing
+/// the WAL, so the copy is complete. An encrypted database stays encrypted with the same key.
+pub fn backup_database(conn: &Connection, db_path: &Path, backups_dir: &Path) -> Result<PathBuf, AppError> {
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+    std::fs::create_dir_all(backups_dir)?;
+    let backup_path = backups_dir.join(format!("app-{}.db", chrono::Utc::now().format("%Y%m%d-%H%M%S")));
+    std::fs::copy(db_path, &backup_path)?;
+    Ok(backup_path)
+}
+
+// Every table a reset clears, children before parents so foreign keys hold while clearing.
+// store_sources and settings are kept: the StoreManager, proxy and manifest-host config and the
+// store caches are loaded from them and would otherwise disagree with the database, and the
+// default store source is only seeded on first launch.
+const APP_TABLES: &[&str] = &[
+    "play_sessions",
+    "game_env_vars",
+    "game_images",
+    "game_metadata",
+    "game_genres",
+    "game_executables",
+    "games",
+    "genres",
+    "platforms",
+    "extension_storage",
+    "extension_settings",
+    "extension_permissions",
+    "extensions",
+    "app_data",
+];
+
+/// Deletes every row from every app table except store sources and settings in one
+/// transaction, keeping the schema.
+/// Returns the number of rows removed per table.
+pub fn clear_all_tables(conn: &Connection) -> Result<std::collections::BTreeMap<String, usize>, AppError> {
+    let tx = write_transaction(conn)?;
+    let mut cleared = std::collections::BTreeMap::new();
+    for table in APP_TABLES {
+        let removed = tx.execute(&format!("DELETE FROM {}", table), [])?;
+        cleared.insert(table.to_string(), removed);
+    }
+    tx.commit()?;
+    Ok(cleared)
+}
 
 pub fn init_database(app: &App) -> Result<(), Box<dyn std::error::Error>> {
     let data_dir = app.path().app_data_dir()?;
-    let db_path = data_dir.join("app.db");
+    let db_path = data_dir.join(DB_FILE_NAME);
     std::fs::create_dir_all(&data_dir)?;
  
-    let conn = Connection::open(db_path)?;
+    let conn = open_connection(&db_path)?;
+    // Persistent, so setting it once here covers every later connection. Readers then
+    // don't block the writer, which leaves busy_timeout to handle writer-writer contention.
+    conn.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))?;
+    create_schema(&conn)?;
+    Ok(())
+}
 
+// Creates missing tables and runs the column migrations; safe to run on every startup
+pub(crate) fn create_schema(conn: &Connection) -> Result<(), rusqlite::Error> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS settings (
             id INTEGER PRIMARY KEY,
@@ -68,6 +205,19 @@ pub fn init_database(app: &App) -> Result<(), Box<dyn std::error::Error>> {
         [],
     )?;
 
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS extension_storage (
+              id INTEGER PRIMARY KEY,
+              extension_id TEXT NOT NULL,
+              key TEXT NOT NULL,
+              value TEXT NOT NULL,
+              updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+              UNIQUE (extension_id, key),
+              FOREIGN KEY (extension_id) REFERENCES extensions(id) ON DELETE CASCADE
+          )",
+        [],
+    )?;
+
     // Store sources table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS store_sources (
@@ -138,31 +288,251 @@ pub fn init_database(app: &App) -> Result<(), Box<dyn std::error::Error>> {
         [],
     )?;
 
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS game_metadata (
+            game_id INTEGER NOT NULL,
+            key TEXT NOT NULL,
+            value TEXT NOT NULL,
+            PRIMARY KEY (game_id, key),
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS game_env_vars (
+            game_id INTEGER NOT NULL,
+            key TEXT NOT NULL,
+            value TEXT NOT NULL,
+            PRIMARY KEY (game_id, key),
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS game_images (
+            game_id INTEGER NOT NULL,
+            image_type TEXT NOT NULL,
+            path TEXT NOT NULL,
+            PRIMARY KEY (game_id, image_type),
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS play_sessions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            game_id INTEGER NOT NULL,
+            started_at TEXT NOT NULL,
+            ended_at TEXT NOT NULL,
+            minutes INTEGER NOT NULL,
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_play_sessions_started_at ON play_sessions(started_at)", [])?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS game_executables (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            game_id INTEGER NOT NULL,
+            label TEXT NOT NULL,
+            path TEXT NOT NULL,
+            arguments TEXT,
+            is_default BOOLEAN DEFAULT 0,
+            UNIQUE (game_id, label),
+            FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Migrations for columns added after the initial schema
+    add_column_if_missing(conn, "extensions", "last_scanned", "DATETIME")?;
+    add_column_if_missing(conn, "extensions", "priority", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "platforms", "base_directory", "TEXT")?;
+    add_column_if_missing(conn, "platforms", "default_working_directory", "TEXT")?;
+    add_column_if_missing(conn, "platforms", "default_cover_path", "TEXT")?;
+    add_column_if_missing(conn, "games", "allow_duplicate", "BOOLEAN DEFAULT 0")?;
+
+    // A game name is unique per platform unless the row was explicitly added as a duplicate.
+    // Libraries that predate the index keep their oldest copy and flag the rest as duplicates.
+    conn.execute(
+        "UPDATE games SET allow_duplicate = 1 WHERE allow_duplicate = 0 AND EXISTS (
+            SELECT 1 FROM games older
+            WHERE older.name = games.name AND older.platform_id = games.platform_id
+              AND older.allow_duplicate = 0 AND older.id < games.id
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_games_name_platform ON games(name, platform_id) WHERE allow_duplicate = 0",
+        [],
+    )?;
+
+    // Games with only the legacy executable_path get it as their default executable
+    conn.execute(
+        "INSERT INTO game_executables (game_id, label, path, arguments, is_default)
+         SELECT id, 'Default', executable_path, arguments, 1 FROM games
+         WHERE executable_path IS NOT NULL AND TRIM(executable_path) != ''
+           AND NOT EXISTS (SELECT 1 FROM game_executables e WHERE e.game_id = games.id)",
+        [],
+    )?;
+    // Older builds left these rows stale after edits; bring them back in line with the games table
+    conn.execute_batch(
+        "DELETE FROM game_executables WHERE label = 'Default'
+           AND game_id IN (SELECT id FROM games WHERE executable_path IS NULL OR TRIM(executable_path) = '');
+         UPDATE game_executables SET path = g.executable_path, arguments = g.arguments
+           FROM games g WHERE g.id = game_executables.game_id AND game_executables.label = 'Default';",
+    )?;
+
+    // Extension settings are upserted per (extension_id, key); keep the newest row of any duplicates
+    conn.execute(
+        "DELETE FROM extension_settings WHERE id NOT IN (SELECT MAX(id) FROM extension_settings GROUP BY extension_id, key)",
+        [],
+    )?;
+    conn.execute(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_extension_settings_key ON extension_settings(extension_id, key)",
+        [],
+    )?;
+
+    // Games added by a GameLibrary extension remember where they came from
+    add_column_if_missing(conn, "games", "source_extension_id", "TEXT")?;
+    add_column_if_missing(conn, "games", "source_game_id", "TEXT")?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_games_source ON games(source_extension_id, source_game_id)",
+        [],
+    )?;
+
+    // Owned-but-not-installed games; existing rows stay installed so they keep launching
+    add_column_if_missing(conn, "games", "installed", "BOOLEAN NOT NULL DEFAULT 1")?;
+
+    // games.cover_image_path stays the cover's source of truth; these keep its game_images row in step
+    conn.execute_batch(
+        "CREATE TRIGGER IF NOT EXISTS trg_games_cover_insert AFTER INSERT ON games WHEN NEW.cover_image_path IS NOT NULL
+         BEGIN
+             INSERT OR REPLACE INTO game_images (game_id, image_type, path) VALUES (NEW.id, 'cover', NEW.cover_image_path);
+         END;
+         CREATE TRIGGER IF NOT EXISTS trg_games_cover_update AFTER UPDATE OF cover_image_path ON games
+         BEGIN
+             DELETE FROM game_images WHERE game_id = NEW.id AND image_type = 'cover' AND NEW.cover_image_path IS NULL;
+             INSERT OR REPLACE INTO game_images (game_id, image_type, path) SELECT NEW.id, 'cover', NEW.cover_image_path WHERE NEW.cover_image_path IS NOT NULL;
+         END;",
+    )?;
+    conn.execute(
+        "INSERT OR IGNORE INTO game_images (game_id, image_type, path) SELECT id, 'cover', cover_image_path FROM games WHERE cover_image_path IS NOT NULL",
+        [],
+    )?;
+
+    // Where store installs came from, so their sources can be checked for updates
+    add_column_if_missing(conn, "extensions", "store_source_id", "TEXT")?;
+    add_column_if_missing(conn, "extensions", "store_extension_id", "TEXT")?;
+
+    Ok(())
+}
+
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<(), rusqlite::Error> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let columns = stmt.query_map([], |row| row.get::<_, String>(1))?;
+    for existing in columns {
+        if existing? == column {
+            return Ok(());
+        }
+    }
+    conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+    Ok(())
+}
+
+// Settings functions
+pub fn get_setting_value(conn: &Connection, key: &str) -> Result<Option<String>, rusqlite::Error> {
+    conn.query_row("SELECT value FROM settings WHERE key = ?", [key], |row| row.get(0)).optional()
+}
+
+pub fn set_setting_value(conn: &Connection, key: &str, value: &str) -> Result<(), rusqlite::Error> {
+    conn.execute(
+        "INSERT INTO settings (key, value) VALUES (?, ?) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        [key, value],
+    )?;
+    Ok(())
+}
+
+pub const FIRST_RUN_COMPLETE_KEY: &str = "first_run_complete";
+
+// Settings the UI reads from the first launch on; existing values are never overwritten
+const DEFAULT_SETTINGS: &[(&str, &str)] = &[("color_scheme", "system"), ("library_view_mode", "grid")];
+const DEFAULT_PLATFORM: &str = "PC";
+
+pub fn first_run_pending(conn: &Connection) -> Result<bool, rusqlite::Error> {
+    Ok(get_setting_value(conn, FIRST_RUN_COMPLETE_KEY)?.as_deref() != Some("true"))
+}
+
+/// Seeds default settings and the default platform, then marks first run as done.
+/// Safe to repeat: nothing that already exists is changed.
+pub fn seed_first_run_defaults(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let tx = write_transaction(conn)?;
+    for (key, value) in DEFAULT_SETTINGS {
+        tx.execute("INSERT INTO settings (key, value) VALUES (?, ?) ON CONFLICT(key) DO NOTHING", [key, value])?;
+    }
+    ensure_platform(&tx, DEFAULT_PLATFORM, None)?;
+    set_setting_value(&tx, FIRST_RUN_COMPLETE_KEY, "true")?;
+    tx.commit()
+}
+
+pub fn get_extension_setting_value(conn: &Connection, extension_id: &str, key: &str) -> Result<Option<String>, rusqlite::Error> {
+    conn.query_row("SELECT value FROM extension_settings WHERE extension_id = ? AND key = ?", [extension_id, key], |row| row.get(0)).optional()
+}
+
+pub fn set_extension_setting_value(conn: &Connection, extension_id: &str, key: &str, value: &str) -> Result<(), rusqlite::Error> {
+    conn.execute(
+        "INSERT INTO extension_settings (extension_id, key, value) VALUES (?, ?, ?)
+         ON CONFLICT(extension_id, key) DO UPDATE SET value = excluded.value",
+        [extension_id, key, value],
+    )?;
+    Ok(())
+}
+
+pub fn delete_setting_value(conn: &Connection, key: &str) -> Result<(), rusqlite::Error> {
+    conn.execute("DELETE FROM settings WHERE key = ?", [key])?;
     Ok(())
 }
 
 // Platform CRUD functions
-pub fn create_platform(conn: &Connection, name: String, description: Option<String>, icon_path: Option<String>) -> Result<i64, rusqlite::Error> {
+pub fn create_platform(conn: &Connection, name: String, description: Option<String>, icon_path: Option<String>, base_directory: Option<String>, default_working_directory: Option<String>, default_cover_path: Option<String>) -> Result<i64, rusqlite::Error> {
     let now = chrono::Utc::now().to_rfc3339();
     conn.execute(
-        "INSERT INTO platforms (name, description, icon_path, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
-        rusqlite::params![name, description, icon_path, now, now],
+        "INSERT INTO platforms (name, description, icon_path, base_directory, default_working_directory, default_cover_path, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        rusqlite::params![name, description, icon_path, base_directory, default_working_directory, default_cover_path, now, now],
     )?;
     Ok(conn.last_insert_rowid())
 }
 
-pub fn get_platforms(conn: &Connection) -> Result<Vec<Platform>, rusqlite::Error> {
-    let mut stmt = conn.prepare("SELECT id, name, description, icon_path, created_at, updated_at FROM platforms")?;
-    let rows = stmt.query_map([], |row| {
-        Ok(Platform {
-            id: row.get(0)?,
-            name: row.get(1)?,
-            description: row.get(2)?,
-            icon_path: row.get(3)?,
-            created_at: row.get(4)?,
-            updated_at: row.get(5)?,
-        })
-    })?;
+const PLATFORM_SELECT: &str = "SELECT id, name, description, icon_path, base_directory, default_working_directory, default_cover_path, created_at, updated_at FROM platforms";
+
+fn row_to_platform(row: &rusqlite::Row) -> Result<Platform, rusqlite::Error> {
+    Ok(Platform {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        icon_path: row.get(3)?,
+        base_directory: row.get(4)?,
+        default_working_directory: row.get(5)?,
+        default_cover_path: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+pub fn get_platforms(conn: &Connection, sort_by: PlatformSort) -> Result<Vec<Platform>, rusqlite::Error> {
+    // Every ordering ends on the id so ties come back in a stable order
+    let order_by = match sort_by {
+        PlatformSort::Name => "name COLLATE NOCASE ASC, id ASC",
+        PlatformSort::CreatedAt => "created_at ASC, id ASC",
+        PlatformSort::GameCount => "(SELECT COUNT(*) FROM games WHERE games.platform_id = platforms.id) DESC, name COLLATE NOCASE ASC, id ASC",
+    };
+    let mut stmt = conn.prepare(&format!("{} ORDER BY {}", PLATFORM_SELECT, order_by))?;
+    let rows = stmt.query_map([], row_to_platform)?;
     let mut platforms = Vec::new();
     for row in rows {
         platforms.push(row?);
@@ -170,21 +540,194 @@ pub fn get_platforms(conn: &Connection) -> Result<Vec<Platform>, rusqlite::Error
     Ok(platforms)
 }
 
-pub fn update_platform(conn: &Connection, id: i64, name: String, description: Option<String>, icon_path: Option<String>) -> Result<(), rusqlite::Error> {
+pub fn update_platform(conn: &Connection, id: i64, name: String, description: Option<String>, icon_path: Option<String>, base_directory: Option<String>, default_working_directory: Option<String>, default_cover_path: Option<String>) -> Result<(), rusqlite::Error> {
     let now = chrono::Utc::now().to_rfc3339();
     conn.execute(
-        "UPDATE platforms SET name = ?, description = ?, icon_path = ?, updated_at = ? WHERE id = ?",
-        rusqlite::params![name, description, icon_path, now, id],
+        "UPDATE platforms SET name = ?, description = ?, icon_path = ?, base_directory = ?, default_working_directory = ?, default_cover_path = ?, updated_at = ? WHERE id = ?",
+        rusqlite::params![name, description, icon_path, base_directory, default_working_directory, default_cover_path, now, id],
     )?;
     Ok(())
 }
 
+// Returns the id of the platform with this name, creating it first if it doesn't exist yet
+pub fn ensure_platform(conn: &Connection, name: &str, icon_path: Option<&str>) -> Result<i64, rusqlite::Error> {
+    let now = chrono::Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT INTO platforms (name, icon_path, created_at, updated_at) VALUES (?, ?, ?, ?) ON CONFLICT(name) DO NOTHING",
+        rusqlite::params![name, icon_path, now, now],
+    )?;
+    conn.query_row("SELECT id FROM platforms WHERE name = ?", [name], |row| row.get(0))
+}
+
+pub fn get_platform(conn: &Connection, id: i64) -> Result<Platform, AppError> {
+    conn.query_row(&format!("{} WHERE id = ?", PLATFORM_SELECT), [id], row_to_platform)
+        .optional()?
+        .ok_or_else(|| AppError::PlatformNotFound { platform_id: id })
+}
+
 pub fn delete_platform(conn: &Connection, id: i64) -> Result<(), rusqlite::Error> {
     conn.execute("DELETE FROM platforms WHERE id = ?", &[&id])?;
     Ok(())
 }
 
+/// Moves every game from `merge_ids` onto `keep_id` and deletes the merged platforms.
+/// A game whose name already exists on the kept platform is folded into that game instead:
+/// its playtime and sessions move over and the duplicate row is dropped. Returns how many
+/// games were moved.
+pub fn merge_platforms(conn: &Connection, keep_id: i64, merge_ids: &[i64]) -> Result<usize, AppError> {
+    if merge_ids.contains(&keep_id) {
+        return Err(AppError::Validation("Cannot merge a platform into itself".to_string()));
+    }
+    let tx = write_transaction(conn)?;
+    get_platform(&tx, keep_id)?;
+
+    let mut moved = 0;
+    for &merge_id in merge_ids {
+        get_platform(&tx, merge_id)?;
+        let games: Vec<(i64, String, bool)> = {
+            let mut stmt = tx.prepare("SELECT id, name, allow_duplicate FROM games WHERE platform_id = ?")?;
+            let rows = stmt.query_map([merge_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+            rows.collect::<Result<_, _>>()?
+        };
+        for (game_id, name, allow_duplicate) in games {
+            let existing: Option<i64> = if allow_duplicate {
+                None
+            } else {
+                tx.query_row(
+                    "SELECT id FROM games WHERE platform_id = ? AND name = ? AND allow_duplicate = 0",
+                    rusqlite::params![keep_id, name],
+                    |row| row.get(0),
+                )
+                .optional()?
+            };
+            match existing {
+                Some(existing_id) => {
+                    tx.execute(
+                        "UPDATE games SET playtime_minutes = playtime_minutes + (SELECT playtime_minutes FROM games WHERE id = ?1),
+                         last_played = MAX(COALESCE(last_played, ''), COALESCE((SELECT last_played FROM games WHERE id = ?1), '')),
+                         cover_image_path = COALESCE(cover_image_path, (SELECT cover_image_path FROM games WHERE id = ?1)),
+                         updated_at = ?3 WHERE id = ?2",
+                        rusqlite::params![game_id, existing_id, chrono::Utc::now().to_rfc3339()],
+                    )?;
+                    tx.execute("UPDATE games SET last_played = NULL WHERE id = ? AND last_played = ''", [existing_id])?;
+                    move_game_children(&tx, game_id, existing_id)?;
+                    tx.execute("DELETE FROM games WHERE id = ?", [game_id])?;
+                }
+                None => {
+                    tx.execute("UPDATE games SET platform_id = ? WHERE id = ?", [keep_id, game_id])?;
+                    moved += 1;
+                }
+            }
+        }
+        tx.execute("DELETE FROM platforms WHERE id = ?", [merge_id])?;
+    }
+    tx.commit()?;
+    Ok(moved)
+}
+
+// Gives `to_id` everything attached to `from_id` before `from_id` is deleted, which would otherwise
+// cascade the rows away. Where both games have a row for the same key, `to_id`'s is kept. The cover
+// image row mirrors games.cover_image_path, so it's left to the caller's update of that column.
+fn move_game_children(tx: &Connection, from_id: i64, to_id: i64) -> Result<(), rusqlite::Error> {
+    tx.execute("UPDATE play_sessions SET game_id = ? WHERE game_id = ?", [to_id, from_id])?;
+    tx.execute("INSERT OR IGNORE INTO game_genres (game_id, genre_id) SELECT ?, genre_id FROM game_genres WHERE game_id = ?", [to_id, from_id])?;
+    tx.execute("INSERT OR IGNORE INTO game_metadata (game_id, key, value) SELECT ?, key, value FROM game_metadata WHERE game_id = ?", [to_id, from_id])?;
+    tx.execute("INSERT OR IGNORE INTO game_env_vars (game_id, key, value) SELECT ?, key, value FROM game_env_vars WHERE game_id = ?", [to_id, from_id])?;
+    tx.execute(
+        "INSERT OR IGNORE INTO game_images (game_id, image_type, path) SELECT ?, image_type, path FROM game_images WHERE game_id = ? AND image_type != 'cover'",
+        [to_id, from_id],
+    )?;
+    // The surviving game keeps its own default executable
+    tx.execute(
+        "INSERT OR IGNORE INTO game_executables (game_id, label, path, arguments, is_default) SELECT ?, label, path, arguments, 0 FROM game_executables WHERE game_id = ?",
+        [to_id, from_id],
+    )?;
+    Ok(())
+}
+
 // Game CRUD functions
+// Games are always read joined to their platform so relative paths and platform defaults can be resolved
+const GAME_SELECT: &str = "SELECT g.id, g.name, g.platform_id, g.description, g.developer, g.publisher, g.release_date, g.cover_image_path, g.executable_path, g.working_directory, g.arguments, g.is_favorite, g.playtime_minutes, g.last_played, g.created_at, g.updated_at, p.base_directory, COALESCE(g.working_directory, p.default_working_directory), p.default_cover_path, g.installed, (SELECT group_concat(image_type) FROM game_images WHERE game_id = g.id) FROM games g LEFT JOIN platforms p ON p.id = g.platform_id";
+
+fn row_to_game(row: &rusqlite::Row) -> Result<Game, rusqlite::Error> {
+    let name: String = row.get(1)?;
+    let cover_image_path: Option<String> = row.get(7)?;
+    let executable_path: Option<String> = row.get(8)?;
+    let base_directory: Option<String> = row.get(16)?;
+    let default_cover_path: Option<String> = row.get(18)?;
+    let resolved_cover_image_path = resolve_path(base_directory.as_deref(), cover_image_path.as_deref());
+    Ok(Game {
+        id: row.get(0)?,
+        display_cover_path: display_cover_path(resolved_cover_image_path.as_deref(), default_cover_path.as_deref(), &name),
+        name,
+        platform_id: row.get(2)?,
+        description: row.get(3)?,
+        developer: row.get(4)?,
+        publisher: row.get(5)?,
+        release_date: row.get(6)?,
+        resolved_cover_image_path,
+        resolved_executable_path: resolve_path(base_directory.as_deref(), executable_path.as_deref()),
+        cover_image_path,
+        executable_path,
+        working_directory: row.get(9)?,
+        arguments: row.get(10)?,
+        is_favorite: row.get(11)?,
+        installed: row.get(19)?,
+        image_types: row
+            .get::<_, Option<String>>(20)?
+            .map(|types| {
+                let mut types: Vec<String> = types.split(',').map(str::to_string).collect();
+                types.sort();
+                types
+            })
+            .unwrap_or_default(),
+        playtime_minutes: row.get(12)?,
+        last_played: row.get(13)?,
+        created_at: row.get(14)?,
+        updated_at: row.get(15)?,
+        effective_working_directory: row.get(17)?,
+    })
+}
+
+fn ensure_platform_exists(conn: &Connection, platform_id: i64) -> Result<(), AppError> {
+    let exists = conn
+        .query_row("SELECT 1 FROM platforms WHERE id = ?", [platform_id], |_| Ok(()))
+        .optional()?;
+    match exists {
+        Some(()) => Ok(()),
+        None => Err(AppError::PlatformNotFound { platform_id }),
+    }
+}
+
+fn ensure_game_exists(conn: &Connection, game_id: i64) -> Result<(), AppError> {
+    let exists = conn
+        .query_row("SELECT 1 FROM games WHERE id = ?", [game_id], |_| Ok(()))
+        .optional()?;
+    match exists {
+        Some(()) => Ok(()),
+        None => Err(AppError::NotFound(format!("Game {} not found", game_id))),
+    }
+}
+
+// Turns a violation of idx_games_name_platform into DuplicateGame pointing at the existing row
+fn map_duplicate_game(conn: &Connection, err: rusqlite::Error, name: &str, platform_id: i64) -> AppError {
+    if let rusqlite::Error::SqliteFailure(failure, _) = &err {
+        if failure.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_UNIQUE {
+            let existing = conn
+                .query_row(
+                    "SELECT id FROM games WHERE name = ? AND platform_id = ? AND allow_duplicate = 0",
+                    rusqlite::params![name, platform_id],
+                    |row| row.get(0),
+                )
+                .optional();
+            if let Ok(Some(existing_id)) = existing {
+                return AppError::DuplicateGame { existing_id };
+            }
+        }
+    }
+    AppError::Database(err)
+}
+
 pub fn create_game(
     conn: &Connection,
     name: String,
@@ -197,37 +740,168 @@ pub fn create_game(
     executable_path: Option<String>,
     working_directory: Option<String>,
     arguments: Option<String>,
-) -> Result<i64, rusqlite::Error> {
+    allow_duplicate: bool,
+    relations: &GameRelations,
+) -> Result<i64, AppError> {
+    // Check and insert in one transaction so the platform can't be deleted in between
+    let tx = write_transaction(conn)?;
+    let id = insert_game(&tx, name, platform_id, description, developer, publisher, release_date, cover_image_path, executable_path, working_directory, arguments, allow_duplicate, relations)?;
+    tx.commit()?;
+    Ok(id)
+}
+
+/// `create_game` for callers that write more rows in the same transaction; `tx` must already be
+/// one (see `write_transaction`).
+pub(crate) fn insert_game(
+    tx: &Connection,
+    name: String,
+    platform_id: i64,
+    description: Option<String>,
+    developer: Option<String>,
+    publisher: Option<String>,
+    release_date: Option<String>,
+    cover_image_path: Option<String>,
+    executable_path: Option<String>,
+    working_directory: Option<String>,
+    arguments: Option<String>,
+    allow_duplicate: bool,
+    relations: &GameRelations,
+) -> Result<i64, AppError> {
     let now = chrono::Utc::now().to_rfc3339();
-    conn.execute(
-        "INSERT INTO games (name, platform_id, description, developer, publisher, release_date, cover_image_path, executable_path, working_directory, arguments, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-        rusqlite::params![name, platform_id, description, developer, publisher, release_date, cover_image_path, executable_path, working_directory, arguments, now, now],
+    ensure_platform_exists(tx, platform_id)?;
+    tx.execute(
+        "INSERT INTO games (name, platform_id, description, developer, publisher, release_date, cover_image_path, executable_path, working_directory, arguments, allow_duplicate, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        rusqlite::params![name, platform_id, description, developer, publisher, release_date, cover_image_path, executable_path, working_directory, arguments, allow_duplicate, now, now],
+    )
+    .map_err(|e| map_duplicate_game(tx, e, &name, platform_id))?;
+    let id = tx.last_insert_rowid();
+    write_game_relations(tx, id, relations)?;
+    sync_default_executable(tx, id)?;
+    Ok(id)
+}
+
+/// Copies a game as a starting point for a similar one: same platform, metadata, paths,
+/// executables and genres, named "<name> (Copy)" with playtime and favorite reset.
+pub fn clone_game(conn: &Connection, id: i64) -> Result<i64, AppError> {
+    let game = get_game(conn, id)?;
+    let name = format!("{} (Copy)", game.name);
+    let now = chrono::Utc::now().to_rfc3339();
+    let tx = write_transaction(conn)?;
+    tx.execute(
+        "INSERT INTO games (name, platform_id, description, developer, publisher, release_date, cover_image_path, executable_path, working_directory, arguments, created_at, updated_at)
+         SELECT ?, platform_id, description, developer, publisher, release_date, cover_image_path, executable_path, working_directory, arguments, ?, ? FROM games WHERE id = ?",
+        rusqlite::params![name, now, now, id],
+    )
+    .map_err(|e| map_duplicate_game(&tx, e, &name, game.platform_id))?;
+    let new_id = tx.last_insert_rowid();
+    tx.execute("INSERT INTO game_genres (game_id, genre_id) SELECT ?, genre_id FROM game_genres WHERE game_id = ?", [new_id, id])?;
+    tx.execute(
+        "INSERT INTO game_executables (game_id, label, path, arguments, is_default) SELECT ?, label, path, arguments, is_default FROM game_executables WHERE game_id = ?",
+        [new_id, id],
     )?;
-    Ok(conn.last_insert_rowid())
+    tx.execute("INSERT INTO game_env_vars (game_id, key, value) SELECT ?, key, value FROM game_env_vars WHERE game_id = ?", [new_id, id])?;
+    // The cover row already came along with cover_image_path
+    tx.execute("INSERT OR IGNORE INTO game_images (game_id, image_type, path) SELECT ?, image_type, path FROM game_images WHERE game_id = ?", [new_id, id])?;
+    tx.commit()?;
+    Ok(new_id)
 }
 
 pub fn get_games(conn: &Connection) -> Result<Vec<Game>, rusqlite::Error> {
-    let mut stmt = conn.prepare("SELECT id, name, platform_id, description, developer, publisher, release_date, cover_image_path, executable_path, working_directory, arguments, is_favorite, playtime_minutes, last_played, created_at, updated_at FROM games")?;
-    let rows = stmt.query_map([], |row| {
-        Ok(Game {
-            id: row.get(0)?,
-            name: row.get(1)?,
-            platform_id: row.get(2)?,
-            description: row.get(3)?,
-            developer: row.get(4)?,
-            publisher: row.get(5)?,
-            release_date: row.get(6)?,
-            cover_image_path: row.get(7)?,
-            executable_path: row.get(8)?,
-            working_directory: row.get(9)?,
-            arguments: row.get(10)?,
-            is_favorite: row.get(11)?,
-            playtime_minutes: row.get(12)?,
-            last_played: row.get(13)?,
-            created_at: row.get(14)?,
-            updated_at: row.get(15)?,
-        })
-    })?;
+    let mut stmt = conn.prepare(GAME_SELECT)?;
+    let rows = stmt.query_map([], row_to_game)?;
+    let mut games = Vec::new();
+    for row in rows {
+        games.push(row?);
+    }
+    Ok(games)
+}
+
+/// Records which extension (and its external id) a game was imported from.
+pub fn set_game_source(conn: &Connection, game_id: i64, extension_id: &str, external_id: &str) -> Result<(), AppError> {
+    let affected = conn.execute(
+        "UPDATE games SET source_extension_id = ?, source_game_id = ? WHERE id = ?",
+        rusqlite::params![extension_id, external_id, game_id],
+    )?;
+    if affected == 0 {
+        return Err(AppError::NotFound(format!("Game {} not found", game_id)));
+    }
+    Ok(())
+}
+
+pub fn set_game_cover(conn: &Connection, game_id: i64, cover_image_path: &str) -> Result<(), AppError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let affected = conn.execute(
+        "UPDATE games SET cover_image_path = ?, updated_at = ? WHERE id = ?",
+        rusqlite::params![cover_image_path, now, game_id],
+    )?;
+    if affected == 0 {
+        return Err(AppError::NotFound(format!("Game {} not found", game_id)));
+    }
+    Ok(())
+}
+
+/// Inserts or updates the game `extension_id` knows as `game.external_id`, matching on the
+/// source columns so repeated syncs don't duplicate it. Playtime and last played are only
+/// overwritten when the extension reports them. Returns the game's id.
+pub fn upsert_source_game(conn: &Connection, extension_id: &str, platform_id: i64, game: &SourceGame) -> Result<i64, AppError> {
+    if game.external_id.trim().is_empty() || game.name.trim().is_empty() {
+        return Err(AppError::Validation("Source games need an external id and a name".to_string()));
+    }
+    let now = chrono::Utc::now().to_rfc3339();
+    let tx = write_transaction(conn)?;
+    ensure_platform_exists(&tx, platform_id)?;
+    let existing: Option<i64> = tx
+        .query_row(
+            "SELECT id FROM games WHERE source_extension_id = ? AND source_game_id = ?",
+            [extension_id, &game.external_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let id = match existing {
+        Some(id) => {
+            tx.execute(
+                "UPDATE games SET name = ?, platform_id = ?, description = ?, developer = ?, publisher = ?, release_date = ?, cover_image_path = ?, executable_path = ?,
+                 playtime_minutes = COALESCE(?, playtime_minutes), last_played = COALESCE(?, last_played), updated_at = ? WHERE id = ?",
+                rusqlite::params![game.name, platform_id, game.description, game.developer, game.publisher, game.release_date, game.cover_image_path, game.executable_path, game.playtime_minutes, game.last_played, now, id],
+            )
+            .map_err(|e| map_duplicate_game(&tx, e, &game.name, platform_id))?;
+            id
+        }
+        None => {
+            tx.execute(
+                "INSERT INTO games (name, platform_id, description, developer, publisher, release_date, cover_image_path, executable_path, playtime_minutes, last_played, source_extension_id, source_game_id, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, COALESCE(?, 0), ?, ?, ?, ?, ?)",
+                rusqlite::params![game.name, platform_id, game.description, game.developer, game.publisher, game.release_date, game.cover_image_path, game.executable_path, game.playtime_minutes, game.last_played, extension_id, game.external_id, now, now],
+            )
+            .map_err(|e| map_duplicate_game(&tx, e, &game.name, platform_id))?;
+            tx.last_insert_rowid()
+        }
+    };
+    sync_default_executable(&tx, id)?;
+    tx.commit()?;
+    Ok(id)
+}
+
+/// The (extension id, external game id) a game was imported from, if any.
+pub fn get_game_source(conn: &Connection, game_id: i64) -> Result<Option<(String, String)>, AppError> {
+    let source: Option<(Option<String>, Option<String>)> = conn
+        .query_row("SELECT source_extension_id, source_game_id FROM games WHERE id = ?", [game_id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .optional()?;
+    match source {
+        None => Err(AppError::NotFound(format!("Game {} not found", game_id))),
+        Some((Some(extension_id), Some(external_id))) => Ok(Some((extension_id, external_id))),
+        Some(_) => Ok(None),
+    }
+}
+
+/// Games on any of `platform_ids`, ordered by name. An empty list matches nothing.
+pub fn get_games_by_platforms(conn: &Connection, platform_ids: &[i64]) -> Result<Vec<Game>, rusqlite::Error> {
+    if platform_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let placeholders = vec!["?"; platform_ids.len()].join(", ");
+    let mut stmt = conn.prepare(&format!("{} WHERE g.platform_id IN ({}) ORDER BY g.name COLLATE NOCASE, g.id", GAME_SELECT, placeholders))?;
+    let rows = stmt.query_map(rusqlite::params_from_iter(platform_ids), row_to_game)?;
     let mut games = Vec::new();
     for row in rows {
         games.push(row?);
@@ -235,28 +909,57 @@ pub fn get_games(conn: &Connection) -> Result<Vec<Game>, rusqlite::Error> {
     Ok(games)
 }
 
+/// Games tagged with the genre called `name` (case-insensitive), ordered by name. An unknown genre matches nothing.
+pub fn get_games_by_genre_name(conn: &Connection, name: &str) -> Result<Vec<Game>, rusqlite::Error> {
+    let mut stmt = conn.prepare(&format!(
+        "{} WHERE EXISTS (SELECT 1 FROM game_genres gg JOIN genres gr ON gr.id = gg.genre_id WHERE gg.game_id = g.id AND gr.name = ? COLLATE NOCASE)
+         ORDER BY g.name COLLATE NOCASE, g.id",
+        GAME_SELECT
+    ))?;
+    let rows = stmt.query_map([name.trim()], row_to_game)?;
+    let mut games = Vec::new();
+    for row in rows {
+        games.push(row?);
+    }
+    Ok(games)
+}
+
+/// Games never launched, oldest additions first.
+pub fn get_unplayed_games(conn: &Connection, limit: u32, offset: u32) -> Result<Vec<Game>, rusqlite::Error> {
+    let mut stmt = conn.prepare(&format!(
+        "{} WHERE g.playtime_minutes = 0 AND g.last_played IS NULL ORDER BY g.created_at ASC, g.id ASC LIMIT ? OFFSET ?",
+        GAME_SELECT
+    ))?;
+    let rows = stmt.query_map([limit, offset], row_to_game)?;
+    let mut games = Vec::new();
+    for row in rows {
+        games.push(row?);
+    }
+    Ok(games)
+}
+
+/// Games created in the last `days` days, newest first. `created_at` is written as UTC RFC3339
+/// (`chrono::Utc::now().to_rfc3339()`), which orders lexically, so the cutoff is compared as a string.
+pub fn get_recently_added(conn: &Connection, days: u32, limit: u32) -> Result<Vec<Game>, rusqlite::Error> {
+    let cutoff = (chrono::Utc::now() - chrono::Duration::days(i64::from(days))).to_rfc3339();
+    let mut stmt = conn.prepare(&format!("{} WHERE g.created_at >= ? ORDER BY g.created_at DESC, g.id DESC LIMIT ?", GAME_SELECT))?;
+    let rows = stmt.query_map(rusqlite::params![cutoff, limit], row_to_game)?;
+    let mut games = Vec::new();
+    for row in rows {
+        games.push(row?);
+    }
+    Ok(games)
+}
+
+pub fn get_game(conn: &Connection, id: i64) -> Result<Game, AppError> {
+    conn.query_row(&format!("{} WHERE g.id = ?", GAME_SELECT), [id], row_to_game)
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Game {} not found", id)))
+}
+
 pub fn get_games_by_platform(conn: &Connection, platform_id: i64) -> Result<Vec<Game>, rusqlite::Error> {
-    let mut stmt = conn.prepare("SELECT id, name, platform_id, description, developer, publisher, release_date, cover_image_path, executable_path, working_directory, arguments, is_favorite, playtime_minutes, last_played, created_at, updated_at FROM games WHERE platform_id = ?")?;
-    let rows = stmt.query_map([platform_id], |row| {
-        Ok(Game {
-            id: row.get(0)?,
-            name: row.get(1)?,
-            platform_id: row.get(2)?,
-            description: row.get(3)?,
-            developer: row.get(4)?,
-            publisher: row.get(5)?,
-            release_date: row.get(6)?,
-            cover_image_path: row.get(7)?,
-            executable_path: row.get(8)?,
-            working_directory: row.get(9)?,
-            arguments: row.get(10)?,
-            is_favorite: row.get(11)?,
-            playtime_minutes: row.get(12)?,
-            last_played: row.get(13)?,
-            created_at: row.get(14)?,
-            updated_at: row.get(15)?,
-        })
-    })?;
+    let mut stmt = conn.prepare(&format!("{} WHERE g.platform_id = ?", GAME_SELECT))?;
+    let rows = stmt.query_map([platform_id], row_to_game)?;
     let mut games = Vec::new();
     for row in rows {
         games.push(row?);
@@ -277,16 +980,677 @@ pub fn update_game(
     executable_path: Option<String>,
     working_directory: Option<String>,
     arguments: Option<String>,
-) -> Result<(), rusqlite::Error> {
+    relations: &GameRelations,
+) -> Result<(), AppError> {
     let now = chrono::Utc::now().to_rfc3339();
-    conn.execute(
+    // The row and its genres/env vars change together or not at all
+    let tx = write_transaction(conn)?;
+    ensure_platform_exists(&tx, platform_id)?;
+    tx.execute(
         "UPDATE games SET name = ?, platform_id = ?, description = ?, developer = ?, publisher = ?, release_date = ?, cover_image_path = ?, executable_path = ?, working_directory = ?, arguments = ?, updated_at = ? WHERE id = ?",
         rusqlite::params![name, platform_id, description, developer, publisher, release_date, cover_image_path, executable_path, working_directory, arguments, now, id],
+    )
+    .map_err(|e| map_duplicate_game(&tx, e, &name, platform_id))?;
+    write_game_relations(&tx, id, relations)?;
+    sync_default_executable(&tx, id)?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Keeps the game's "Default" executable row in step with `games.executable_path`/`arguments`,
+/// which is what the startup migration seeded it from. The row is dropped when the path is cleared,
+/// and only becomes the launch default if no other executable already is. Runs inside the caller's transaction.
+pub fn sync_default_executable(conn: &Connection, game_id: i64) -> Result<(), rusqlite::Error> {
+    let (path, arguments): (Option<String>, Option<String>) =
+        conn.query_row("SELECT executable_path, arguments FROM games WHERE id = ?", [game_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    match path.filter(|path| !path.trim().is_empty()) {
+        Some(path) => {
+            conn.execute(
+                "INSERT INTO game_executables (game_id, label, path, arguments, is_default)
+                 VALUES (?1, 'Default', ?2, ?3, NOT EXISTS (SELECT 1 FROM game_executables WHERE game_id = ?1 AND is_default = 1))
+                 ON CONFLICT(game_id, label) DO UPDATE SET path = excluded.path, arguments = excluded.arguments",
+                rusqlite::params![game_id, path, arguments],
+            )?;
+        }
+        None => {
+            conn.execute("DELETE FROM game_executables WHERE game_id = ? AND label = 'Default'", [game_id])?;
+        }
+    }
+    Ok(())
+}
+
+// Replaces the genre and env var sets the caller provided. Runs inside the caller's transaction.
+fn write_game_relations(conn: &Connection, game_id: i64, relations: &GameRelations) -> Result<(), AppError> {
+    if let Some(genres) = &relations.genres {
+        conn.execute("DELETE FROM game_genres WHERE game_id = ?", [game_id])?;
+        for genre in genres.iter().map(|genre| genre.trim()).filter(|genre| !genre.is_empty()) {
+            conn.execute("INSERT INTO genres (name) VALUES (?) ON CONFLICT(name) DO NOTHING", [genre])?;
+            conn.execute(
+                "INSERT OR IGNORE INTO game_genres (game_id, genre_id) SELECT ?, id FROM genres WHERE name = ?",
+                rusqlite::params![game_id, genre],
+            )?;
+        }
+    }
+    if let Some(env_vars) = &relations.env_vars {
+        conn.execute("DELETE FROM game_env_vars WHERE game_id = ?", [game_id])?;
+        for (key, value) in env_vars {
+            validate_env_var_key(key)?;
+            conn.execute("INSERT INTO game_env_vars (game_id, key, value) VALUES (?, ?, ?)", rusqlite::params![game_id, key, value])?;
+        }
+    }
+    Ok(())
+}
+
+// Increments playtime in place so concurrent writers never overwrite each other's minutes
+pub fn add_playtime(conn: &Connection, game_id: i64, minutes: i64) -> Result<i64, AppError> {
+    if minutes < 0 {
+        return Err(AppError::Validation(format!("Playtime minutes cannot be negative (got {})", minutes)));
+    }
+    let now = chrono::Utc::now().to_rfc3339();
+    let affected = conn.execute(
+        "UPDATE games SET playtime_minutes = playtime_minutes + ?, last_played = ?, updated_at = ? WHERE id = ?",
+        rusqlite::params![minutes, now, now, game_id],
     )?;
+    if affected == 0 {
+        return Err(AppError::NotFound(format!("Game {} not found", game_id)));
+    }
+    Ok(conn.query_row("SELECT playtime_minutes FROM games WHERE id = ?", [game_id], |row| row.get(0))?)
+}
+
+pub fn set_game_installed(conn: &Connection, id: i64, installed: bool) -> Result<(), AppError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let affected = conn.execute("UPDATE games SET installed = ?, updated_at = ? WHERE id = ?", rusqlite::params![installed, now, id])?;
+    if affected == 0 {
+        return Err(AppError::NotFound(format!("Game {} not found", id)));
+    }
     Ok(())
 }
 
+/// Sets `is_favorite` on every game in `ids` and returns how many rows changed.
+pub fn set_games_favorite(conn: &Connection, ids: &[i64], is_favorite: bool) -> Result<usize, rusqlite::Error> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    let placeholders = vec!["?"; ids.len()].join(", ");
+    let mut params: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(is_favorite), Box::new(chrono::Utc::now().to_rfc3339())];
+    params.extend(ids.iter().map(|id| Box::new(*id) as Box<dyn rusqlite::ToSql>));
+    let tx = write_transaction(conn)?;
+    let changed = tx.execute(
+        &format!("UPDATE games SET is_favorite = ?, updated_at = ? WHERE id IN ({}) AND is_favorite != ?1", placeholders),
+        rusqlite::params_from_iter(params.iter()),
+    )?;
+    tx.commit()?;
+    Ok(changed)
+}
+
+/// Records a finished play session and adds its minutes to the game's cumulative playtime.
+/// Returns the new total.
+pub fn record_play_session(conn: &Connection, game_id: i64, started_at: &str, ended_at: &str, minutes: i64) -> Result<i64, AppError> {
+    let tx = write_transaction(conn)?;
+    let total = add_playtime(&tx, game_id, minutes)?;
+    tx.execute(
+        "INSERT INTO play_sessions (game_id, started_at, ended_at, minutes) VALUES (?, ?, ?, ?)",
+        rusqlite::params![game_id, started_at, ended_at, minutes],
+    )?;
+    tx.commit()?;
+    Ok(total)
+}
+
+/// Sums session minutes for sessions that started within `[from, to)`. Bounds are compared as
+/// RFC 3339 strings, so plain dates like "2025-01-01" work too.
+pub fn get_playtime_report(conn: &Connection, from: &str, to: &str) -> Result<PlaytimeReport, rusqlite::Error> {
+    let total_minutes: i64 = conn.query_row(
+        "SELECT COALESCE(SUM(minutes), 0) FROM play_sessions WHERE started_at >= ? AND started_at < ?",
+        [from, to],
+        |row| row.get(0),
+    )?;
+
+    let mut stmt = conn.prepare(
+        "SELECT g.id, g.name, SUM(s.minutes) AS total FROM play_sessions s JOIN games g ON g.id = s.game_id
+         WHERE s.started_at >= ? AND s.started_at < ? GROUP BY g.id ORDER BY total DESC, g.name",
+    )?;
+    let rows = stmt.query_map([from, to], |row| Ok(PlaytimeEntry { id: row.get(0)?, name: row.get(1)?, minutes: row.get(2)? }))?;
+    let mut by_game = Vec::new();
+    for row in rows {
+        by_game.push(row?);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT p.id, p.name, SUM(s.minutes) AS total FROM play_sessions s JOIN games g ON g.id = s.game_id JOIN platforms p ON p.id = g.platform_id
+         WHERE s.started_at >= ? AND s.started_at < ? GROUP BY p.id ORDER BY total DESC, p.name",
+    )?;
+    let rows = stmt.query_map([from, to], |row| Ok(PlaytimeEntry { id: row.get(0)?, name: row.get(1)?, minutes: row.get(2)? }))?;
+    let mut by_platform = Vec::new();
+    for row in rows {
+        by_platform.push(row?);
+    }
+
+    Ok(PlaytimeReport { from: from.to_string(), to: to.to_string(), total_minutes, by_game, by_platform })
+}
+
 pub fn delete_game(conn: &Connection, id: i64) -> Result<(), rusqlite::Error> {
     conn.execute("DELETE FROM games WHERE id = ?", &[&id])?;
     Ok(())
-}
\ No newline at end of file
+}
+// Game executable functions
+const GAME_EXECUTABLE_SELECT: &str = "SELECT id, game_id, label, path, arguments, is_default FROM game_executables";
+
+fn row_to_game_executable(row: &rusqlite::Row) -> Result<GameExecutable, rusqlite::Error> {
+    Ok(GameExecutable {
+        id: row.get(0)?,
+        game_id: row.get(1)?,
+        label: row.get(2)?,
+        path: row.get(3)?,
+        arguments: row.get(4)?,
+        is_default: row.get(5)?,
+    })
+}
+
+pub fn add_game_executable(conn: &Connection, game_id: i64, label: String, path: String, arguments: Option<String>, is_default: bool) -> Result<i64, AppError> {
+    let tx = write_transaction(conn)?;
+    // Only one executable per game can be the default
+    if is_default {
+        tx.execute("UPDATE game_executables SET is_default = 0 WHERE game_id = ?", [game_id])?;
+    }
+    tx.execute(
+        "INSERT INTO game_executables (game_id, label, path, arguments, is_default) VALUES (?, ?, ?, ?, ?)",
+        rusqlite::params![game_id, label, path, arguments, is_default],
+    )?;
+    let id = tx.last_insert_rowid();
+    tx.commit()?;
+    Ok(id)
+}
+
+pub fn get_game_executables(conn: &Connection, game_id: i64) -> Result<Vec<GameExecutable>, rusqlite::Error> {
+    let mut stmt = conn.prepare(&format!("{} WHERE game_id = ? ORDER BY is_default DESC, label", GAME_EXECUTABLE_SELECT))?;
+    let rows = stmt.query_map([game_id], row_to_game_executable)?;
+    let mut executables = Vec::new();
+    for row in rows {
+        executables.push(row?);
+    }
+    Ok(executables)
+}
+
+// Finds the executable with the given label, or the game's default executable when no label is given
+pub fn find_game_executable(conn: &Connection, game_id: i64, label: Option<&str>) -> Result<Option<GameExecutable>, rusqlite::Error> {
+    match label {
+        Some(label) => conn
+            .query_row(&format!("{} WHERE game_id = ? AND label = ?", GAME_EXECUTABLE_SELECT), rusqlite::params![game_id, label], row_to_game_executable)
+            .optional(),
+        None => conn
+            .query_row(&format!("{} WHERE game_id = ? AND is_default = 1", GAME_EXECUTABLE_SELECT), [game_id], row_to_game_executable)
+            .optional(),
+    }
+}
+
+pub fn delete_game_executable(conn: &Connection, id: i64) -> Result<usize, rusqlite::Error> {
+    conn.execute("DELETE FROM game_executables WHERE id = ?", [id])
+}
+
+// Genre functions
+/// Tags every game in `game_ids` with the genre, skipping games already tagged.
+/// Returns how many tags were actually added.
+pub fn add_genre_to_games(conn: &Connection, genre_id: i64, game_ids: &[i64]) -> Result<usize, AppError> {
+    let exists = conn.query_row("SELECT 1 FROM genres WHERE id = ?", [genre_id], |_| Ok(())).optional()?;
+    if exists.is_none() {
+        return Err(AppError::NotFound(format!("Genre {} not found", genre_id)));
+    }
+    let tx = write_transaction(conn)?;
+    let mut added = 0;
+    {
+        let mut stmt = tx.prepare("INSERT OR IGNORE INTO game_genres (game_id, genre_id) VALUES (?, ?)")?;
+        for &game_id in game_ids {
+            added += stmt.execute([game_id, genre_id])?;
+        }
+    }
+    tx.commit()?;
+    Ok(added)
+}
+
+pub fn get_genres_with_counts(conn: &Connection) -> Result<Vec<GenreWithCount>, rusqlite::Error> {
+    let mut stmt = conn.prepare(
+        "SELECT g.id, g.name, COUNT(gg.game_id) FROM genres g LEFT JOIN game_genres gg ON gg.genre_id = g.id
+         GROUP BY g.id ORDER BY g.name COLLATE NOCASE",
+    )?;
+    let rows = stmt.query_map([], |row| Ok(GenreWithCount { id: row.get(0)?, name: row.get(1)?, game_count: row.get(2)? }))?;
+    let mut genres = Vec::new();
+    for row in rows {
+        genres.push(row?);
+    }
+    Ok(genres)
+}
+
+/// Removes genres no game is tagged with. Returns how many were deleted.
+pub fn delete_unused_genres(conn: &Connection) -> Result<usize, rusqlite::Error> {
+    conn.execute("DELETE FROM genres WHERE id NOT IN (SELECT genre_id FROM game_genres)", [])
+}
+
+/// Folds `merge_ids` into the `keep_id` genre: their games are tagged with the kept genre and
+/// the merged genres are deleted. Returns how many associations were moved; a game already
+/// tagged with the kept genre isn't counted.
+pub fn merge_genres(conn: &Connection, keep_id: i64, merge_ids: &[i64]) -> Result<usize, AppError> {
+    if merge_ids.contains(&keep_id) {
+        return Err(AppError::Validation("Cannot merge a genre into itself".to_string()));
+    }
+    let tx = write_transaction(conn)?;
+    let mut moved = 0;
+    for &genre_id in std::iter::once(&keep_id).chain(merge_ids) {
+        let exists = tx.query_row("SELECT 1 FROM genres WHERE id = ?", [genre_id], |_| Ok(())).optional()?;
+        if exists.is_none() {
+            return Err(AppError::NotFound(format!("Genre {} not found", genre_id)));
+        }
+        if genre_id == keep_id {
+            continue;
+        }
+        moved += tx.execute("INSERT OR IGNORE INTO game_genres (game_id, genre_id) SELECT game_id, ? FROM game_genres WHERE genre_id = ?", [keep_id, genre_id])?;
+        tx.execute("DELETE FROM game_genres WHERE genre_id = ?", [genre_id])?;
+        tx.execute("DELETE FROM genres WHERE id = ?", [genre_id])?;
+    }
+    tx.commit()?;
+    Ok(moved)
+}
+
+// Game metadata functions
+pub fn set_game_metadata(conn: &Connection, game_id: i64, key: String, value: String) -> Result<(), rusqlite::Error> {
+    conn.execute(
+        "INSERT INTO game_metadata (game_id, key, value) VALUES (?, ?, ?)
+         ON CONFLICT(game_id, key) DO UPDATE SET value = excluded.value",
+        rusqlite::params![game_id, key, value],
+    )?;
+    Ok(())
+}
+
+pub fn get_game_metadata(conn: &Connection, game_id: i64) -> Result<HashMap<String, String>, rusqlite::Error> {
+    let mut stmt = conn.prepare("SELECT key, value FROM game_metadata WHERE game_id = ?")?;
+    let rows = stmt.query_map([game_id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+    let mut metadata = HashMap::new();
+    for row in rows {
+        let (key, value) = row?;
+        metadata.insert(key, value);
+    }
+    Ok(metadata)
+}
+
+pub fn delete_game_metadata(conn: &Connection, game_id: i64, key: String) -> Result<usize, rusqlite::Error> {
+    conn.execute("DELETE FROM game_metadata WHERE game_id = ? AND key = ?", rusqlite::params![game_id, key])
+}
+
+pub const GAME_IMAGE_TYPES: &[&str] = &["cover", "banner", "logo", "background", "icon"];
+
+fn validate_image_type(image_type: &str) -> Result<(), AppError> {
+    if GAME_IMAGE_TYPES.contains(&image_type) {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!("Unknown image type '{}', expected one of {}", image_type, GAME_IMAGE_TYPES.join(", "))))
+    }
+}
+
+/// Sets one of a game's images. The cover is written to `cover_image_path`, which a trigger
+/// mirrors into game_images, so existing cover handling keeps working.
+pub fn set_game_image(conn: &Connection, game_id: i64, image_type: &str, path: &str) -> Result<(), AppError> {
+    validate_image_type(image_type)?;
+    if image_type == "cover" {
+        return set_game_cover(conn, game_id, path);
+    }
+    ensure_game_exists(conn, game_id)?;
+    conn.execute(
+        "INSERT INTO game_images (game_id, image_type, path) VALUES (?, ?, ?)
+         ON CONFLICT(game_id, image_type) DO UPDATE SET path = excluded.path",
+        rusqlite::params![game_id, image_type, path],
+    )?;
+    Ok(())
+}
+
+/// Image type to path for every image a game has.
+pub fn get_game_images(conn: &Connection, game_id: i64) -> Result<std::collections::BTreeMap<String, String>, rusqlite::Error> {
+    let mut stmt = conn.prepare("SELECT image_type, path FROM game_images WHERE game_id = ?")?;
+    let rows = stmt.query_map([game_id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+    let mut images = std::collections::BTreeMap::new();
+    for row in rows {
+        let (image_type, path) = row?;
+        images.insert(image_type, path);
+    }
+    Ok(images)
+}
+
+pub fn delete_game_image(conn: &Connection, game_id: i64, image_type: &str) -> Result<usize, AppError> {
+    validate_image_type(image_type)?;
+    if image_type == "cover" {
+        let now = chrono::Utc::now().to_rfc3339();
+        return Ok(conn.execute(
+            "UPDATE games SET cover_image_path = NULL, updated_at = ? WHERE id = ? AND cover_image_path IS NOT NULL",
+            rusqlite::params![now, game_id],
+        )?);
+    }
+    Ok(conn.execute("DELETE FROM game_images WHERE game_id = ? AND image_type = ?", rusqlite::params![game_id, image_type])?)
+}
+
+// Game environment variables, applied when the game is launched
+fn validate_env_var_key(key: &str) -> Result<(), AppError> {
+    if key.trim().is_empty() || key.contains('=') || key.contains('\0') {
+        return Err(AppError::Validation(format!("Invalid environment variable name '{}'", key)));
+    }
+    Ok(())
+}
+
+pub fn set_game_env_var(conn: &Connection, game_id: i64, key: &str, value: &str) -> Result<(), AppError> {
+    validate_env_var_key(key)?;
+    ensure_game_exists(conn, game_id)?;
+    conn.execute(
+        "INSERT INTO game_env_vars (game_id, key, value) VALUES (?, ?, ?)
+         ON CONFLICT(game_id, key) DO UPDATE SET value = excluded.value",
+        rusqlite::params![game_id, key, value],
+    )?;
+    Ok(())
+}
+
+pub fn get_game_env_vars(conn: &Connection, game_id: i64) -> Result<HashMap<String, String>, rusqlite::Error> {
+    let mut stmt = conn.prepare("SELECT key, value FROM game_env_vars WHERE game_id = ?")?;
+    let rows = stmt.query_map([game_id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+    let mut env_vars = HashMap::new();
+    for row in rows {
+        let (key, value) = row?;
+        env_vars.insert(key, value);
+    }
+    Ok(env_vars)
+}
+
+pub fn delete_game_env_var(conn: &Connection, game_id: i64, key: &str) -> Result<usize, rusqlite::Error> {
+    conn.execute("DELETE FROM game_env_vars WHERE game_id = ? AND key = ?", rusqlite::params![game_id, key])
+}
+
+// Filter facets
+fn get_distinct_game_column(conn: &Connection, column: &str) -> Result<Vec<String>, rusqlite::Error> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT DISTINCT {column} FROM games WHERE {column} IS NOT NULL AND TRIM({column}) != '' ORDER BY {column} COLLATE NOCASE",
+        column = column
+    ))?;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+    let mut values = Vec::new();
+    for row in rows {
+        values.push(row?);
+    }
+    Ok(values)
+}
+
+pub fn get_distinct_developers(conn: &Connection) -> Result<Vec<String>, rusqlite::Error> {
+    get_distinct_game_column(conn, "developer")
+}
+
+pub fn get_distinct_publishers(conn: &Connection) -> Result<Vec<String>, rusqlite::Error> {
+    get_distinct_game_column(conn, "publisher")
+}
+
+// Search functions
+// Association tables and how to spot rows whose parent is gone: (table, description, condition).
+// Databases created before foreign keys were enforced can contain these.
+const ORPHAN_CHECKS: &[(&str, &str, &str)] = &[
+    ("game_genres", "'game ' || game_id || ', genre ' || genre_id", "game_id NOT IN (SELECT id FROM games) OR genre_id NOT IN (SELECT id FROM genres)"),
+    ("game_executables", "'game ' || game_id", "game_id NOT IN (SELECT id FROM games)"),
+    ("game_metadata", "'game ' || game_id", "game_id NOT IN (SELECT id FROM games)"),
+    ("game_env_vars", "'game ' || game_id", "game_id NOT IN (SELECT id FROM games)"),
+    ("game_images", "'game ' || game_id", "game_id NOT IN (SELECT id FROM games)"),
+    ("play_sessions", "'game ' || game_id", "game_id NOT IN (SELECT id FROM games)"),
+    ("extension_permissions", "'extension ' || extension_id", "extension_id NOT IN (SELECT id FROM extensions)"),
+    ("extension_settings", "'extension ' || extension_id", "extension_id NOT IN (SELECT id FROM extensions)"),
+    ("extension_storage", "'extension ' || extension_id", "extension_id NOT IN (SELECT id FROM extensions)"),
+];
+
+pub fn find_orphaned_associations(conn: &Connection) -> Result<Vec<OrphanedRow>, rusqlite::Error> {
+    let mut orphans = Vec::new();
+    for (table, description, condition) in ORPHAN_CHECKS {
+        let mut stmt = conn.prepare(&format!("SELECT rowid, {} FROM {} WHERE {}", description, table, condition))?;
+        let rows = stmt.query_map([], |row| {
+            Ok(OrphanedRow { table: table.to_string(), rowid: row.get(0)?, references: row.get(1)? })
+        })?;
+        for row in rows {
+            orphans.push(row?);
+        }
+    }
+    Ok(orphans)
+}
+
+/// Deletes every orphaned association row in one transaction and returns how many were removed.
+pub fn repair_orphaned_associations(conn: &Connection) -> Result<usize, rusqlite::Error> {
+    let tx = write_transaction(conn)?;
+    let mut removed = 0;
+    for (table, _, condition) in ORPHAN_CHECKS {
+        removed += tx.execute(&format!("DELETE FROM {} WHERE {}", table, condition), [])?;
+    }
+    tx.commit()?;
+    Ok(removed)
+}
+
+fn like_pattern(query: &str) -> String {
+    let escaped = query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+pub fn search_games(conn: &Connection, query: &str, limit: u32) -> Result<Vec<Game>, rusqlite::Error> {
+    let mut stmt = conn.prepare(&format!("{} WHERE g.name LIKE ? ESCAPE '\\' ORDER BY g.name COLLATE NOCASE LIMIT ?", GAME_SELECT))?;
+    let rows = stmt.query_map(rusqlite::params![like_pattern(query), limit], row_to_game)?;
+    let mut games = Vec::new();
+    for row in rows {
+        games.push(row?);
+    }
+    Ok(games)
+}
+
+pub fn query_games(conn: &Connection, filter: &GameFilter) -> Result<Vec<Game>, rusqlite::Error> {
+    let mut conditions = Vec::new();
+    let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+    if let Some(platform_id) = filter.platform_id {
+        conditions.push("g.platform_id = ?");
+        params.push(Box::new(platform_id));
+    }
+    if let Some(search) = filter.search.as_deref().filter(|s| !s.trim().is_empty()) {
+        conditions.push("g.name LIKE ? ESCAPE '\\'");
+        params.push(Box::new(like_pattern(search)));
+    }
+    if filter.favorites_only {
+        conditions.push("g.is_favorite = 1");
+    }
+    if let Some(installed) = filter.installed {
+        conditions.push("g.installed = ?");
+        params.push(Box::new(installed));
+    }
+    match (filter.min_playtime_minutes, filter.max_playtime_minutes) {
+        (Some(min), Some(max)) => {
+            conditions.push("g.playtime_minutes BETWEEN ? AND ?");
+            params.push(Box::new(min));
+            params.push(Box::new(max));
+        }
+        (Some(min), None) => {
+            conditions.push("g.playtime_minutes >= ?");
+            params.push(Box::new(min));
+        }
+        (None, Some(max)) => {
+            conditions.push("g.playtime_minutes <= ?");
+            params.push(Box::new(max));
+        }
+        (None, None) => {}
+    }
+    let where_clause = if conditions.is_empty() { String::new() } else { format!(" WHERE {}", conditions.join(" AND ")) };
+    // LIMIT -1 means no limit in SQLite
+    params.push(Box::new(filter.limit.map(i64::from).unwrap_or(-1)));
+    params.push(Box::new(filter.offset.unwrap_or(0)));
+
+    let mut stmt = conn.prepare(&format!("{}{} ORDER BY g.name COLLATE NOCASE LIMIT ? OFFSET ?", GAME_SELECT, where_clause))?;
+    let rows = stmt.query_map(rusqlite::params_from_iter(params.iter()), row_to_game)?;
+    let mut games = Vec::new();
+    for row in rows {
+        games.push(row?);
+    }
+    Ok(games)
+}
+
+pub fn search_platforms(conn: &Connection, query: &str, limit: u32) -> Result<Vec<Platform>, rusqlite::Error> {
+    let mut stmt = conn.prepare(&format!("{} WHERE name LIKE ? ESCAPE '\\' ORDER BY name COLLATE NOCASE LIMIT ?", PLATFORM_SELECT))?;
+    let rows = stmt.query_map(rusqlite::params![like_pattern(query), limit], row_to_platform)?;
+    let mut platforms = Vec::new();
+    for row in rows {
+        platforms.push(row?);
+    }
+    Ok(platforms)
+}
+
+// Library statistics
+pub fn get_library_stats(conn: &Connection) -> Result<LibraryStats, rusqlite::Error> {
+    let (total_games, total_playtime_minutes, favorite_count): (i64, i64, i64) = conn.query_row(
+        "SELECT COUNT(*), COALESCE(SUM(playtime_minutes), 0), COALESCE(SUM(CASE WHEN is_favorite THEN 1 ELSE 0 END), 0) FROM games",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    )?;
+    let total_platforms: i64 = conn.query_row("SELECT COUNT(*) FROM platforms", [], |row| row.get(0))?;
+
+    let most_played_game = conn
+        .query_row(
+            &format!("{} ORDER BY g.playtime_minutes DESC, g.id ASC LIMIT 1", GAME_SELECT),
+            [],
+            row_to_game,
+        )
+        .optional()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT p.name, COUNT(g.id) FROM platforms p LEFT JOIN games g ON g.platform_id = p.id GROUP BY p.id ORDER BY p.name",
+    )?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?;
+    let mut games_by_platform = Vec::new();
+    for row in rows {
+        games_by_platform.push(row?);
+    }
+
+    Ok(LibraryStats {
+        total_games,
+        total_platforms,
+        total_playtime_minutes,
+        favorite_count,
+        most_played_game,
+        games_by_platform,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_connection() -> Connection {
+        let conn = open_connection(Path::new(":memory:")).unwrap();
+        create_schema(&conn).unwrap();
+        conn
+    }
+
+    fn game_count(conn: &Connection) -> i64 {
+        conn.query_row("SELECT COUNT(*) FROM games", [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn missing_platform_reports_its_id() {
+        let conn = test_connection();
+
+        assert!(matches!(get_platform(&conn, 42), Err(AppError::PlatformNotFound { platform_id: 42 })));
+        let result = create_game(&conn, "Game".to_string(), 42, None, None, None, None, None, None, None, None, false, &GameRelations::default());
+        assert!(matches!(result, Err(AppError::PlatformNotFound { platform_id: 42 })));
+    }
+
+    #[test]
+    fn create_game_rolls_back_when_genres_fail() {
+        let conn = test_connection();
+        let platform_id = create_platform(&conn, "PC".to_string(), None, None, None, None, None).unwrap();
+        conn.execute_batch(
+            "CREATE TRIGGER fail_game_genres BEFORE INSERT ON game_genres
+             BEGIN SELECT RAISE(ABORT, 'genre insert failed'); END;",
+        )
+        .unwrap();
+        let relations = GameRelations { genres: Some(vec!["RPG".to_string()]), env_vars: None };
+
+        let result = create_game(&conn, "Game".to_string(), platform_id, None, None, None, None, None, None, None, None, false, &relations);
+
+        assert!(result.is_err());
+        assert_eq!(game_count(&conn), 0);
+    }
+
+    #[test]
+    fn deleting_a_platform_deletes_its_games() {
+        let conn = test_connection();
+        let platform_id = create_platform(&conn, "PC".to_string(), None, None, None, None, None).unwrap();
+        create_game(&conn, "Game".to_string(), platform_id, None, None, None, None, None, None, None, None, false, &GameRelations::default()).unwrap();
+
+        delete_platform(&conn, platform_id).unwrap();
+
+        assert_eq!(game_count(&conn), 0);
+    }
+
+    #[test]
+    fn setting_a_setting_twice_updates_the_same_row() {
+        let conn = test_connection();
+        let rowid = |conn: &Connection| -> i64 { conn.query_row("SELECT rowid FROM settings WHERE key = 'theme'", [], |row| row.get(0)).unwrap() };
+
+        set_setting_value(&conn, "theme", "dark").unwrap();
+        let first = rowid(&conn);
+        set_setting_value(&conn, "theme", "light").unwrap();
+
+        assert_eq!(rowid(&conn), first);
+        assert_eq!(get_setting_value(&conn, "theme").unwrap().as_deref(), Some("light"));
+    }
+
+    #[test]
+    fn platform_order_breaks_ties_by_id() {
+        let conn = test_connection();
+        for name in ["b", "A", "a"] {
+            create_platform(&conn, name.to_string(), None, None, None, None, None).unwrap();
+        }
+        conn.execute("UPDATE platforms SET created_at = '2025-01-01T00:00:00Z'", []).unwrap();
+        let ids = |sort_by| -> Vec<i64> { get_platforms(&conn, sort_by).unwrap().iter().map(|platform| platform.id).collect() };
+
+        assert_eq!(ids(PlatformSort::Name), vec![2, 3, 1]);
+        assert_eq!(ids(PlatformSort::CreatedAt), vec![1, 2, 3]);
+        assert_eq!(ids(PlatformSort::GameCount), vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn playtime_filter_includes_its_bounds() {
+        let conn = test_connection();
+        let platform_id = create_platform(&conn, "PC".to_string(), None, None, None, None, None).unwrap();
+        for (name, minutes) in [("Short", 59), ("Exact", 60), ("Long", 120)] {
+            let id = create_game(&conn, name.to_string(), platform_id, None, None, None, None, None, None, None, None, false, &GameRelations::default()).unwrap();
+            add_playtime(&conn, id, minutes).unwrap();
+        }
+        let filter = GameFilter { min_playtime_minutes: Some(60), max_playtime_minutes: Some(120), ..Default::default() };
+
+        let mut names: Vec<String> = query_games(&conn, &filter).unwrap().into_iter().map(|game| game.name).collect();
+        names.sort();
+
+        assert_eq!(names, vec!["Exact", "Long"]);
+    }
+
+    #[test]
+    fn concurrent_inserts_wait_for_the_lock() {
+        let dir = std::env::temp_dir().join(format!("arcadia-busy-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join(DB_FILE_NAME);
+        let conn = open_connection(&db_path).unwrap();
+        conn.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(())).unwrap();
+        create_schema(&conn).unwrap();
+        let platform_id = create_platform(&conn, "PC".to_string(), None, None, None, None, None).unwrap();
+
+        let threads: Vec<_> = (0..4)
+            .map(|thread| {
+                let db_path = db_path.clone();
+                std::thread::spawn(move || {
+                    let conn = open_connection(&db_path).unwrap();
+                    for i in 0..25 {
+                        create_game(&conn, format!("Game {}-{}", thread, i), platform_id, None, None, None, None, None, None, None, None, false, &GameRelations::default()).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(game_count(&conn), 100);
+        drop(conn);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,175 @@
+use crate::error::AppError;
+use std::path::Path;
+use tauri::AppHandle;
+
+pub const ENCRYPTED_DB_KEY: &str = "encrypted_db";
+
+#[cfg(feature = "sqlcipher")]
+mod sqlcipher {
+    use super::ENCRYPTED_DB_KEY;
+    use crate::database::{get_db_path, open_connection, set_setting_value};
+    use crate::error::AppError;
+    use rusqlite::{Connection, OptionalExtension};
+    use std::path::Path;
+    use std::sync::RwLock;
+    use tauri::AppHandle;
+
+    const KEYRING_SERVICE: &str = "arcadia-app";
+    const KEYRING_USER: &str = "database-key";
+
+    // Resolved once per run: `None` until the first connection, then `Some(None)` for a
+    // plaintext database or `Some(Some(key))` for an encrypted one.
+    static DB_KEY: RwLock<Option<Option<String>>> = RwLock::new(None);
+
+    fn keyring_entry() -> Result<keyring::Entry, AppError> {
+        keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER).map_err(keyring_error)
+    }
+
+    fn keyring_error(e: keyring::Error) -> AppError {
+        AppError::Io(std::io::Error::new(std::io::ErrorKind::Other, format!("Keychain error: {}", e)))
+    }
+
+    // The `encrypted_db` setting is only ever written into the encrypted copy, so a database
+    // that reads without a key is plaintext unless it says otherwise, and one that can't be
+    // read at all (SQLITE_NOTADB) is encrypted.
+    fn is_encrypted(db_path: &Path) -> Result<bool, AppError> {
+        if !db_path.exists() {
+            return Ok(false);
+        }
+        let conn = Connection::open(db_path)?;
+        let has_settings = conn.query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'settings'",
+            [],
+            |_| Ok(()),
+        );
+        match has_settings.optional() {
+            Ok(Some(())) => {}
+            Ok(None) => return Ok(false),
+            Err(rusqlite::Error::SqliteFailure(e, _)) if e.code == rusqlite::ErrorCode::NotADatabase => return Ok(true),
+            Err(e) => return Err(e.into()),
+        }
+        let value: Option<String> = conn
+            .query_row("SELECT value FROM settings WHERE key = ?", [ENCRYPTED_DB_KEY], |row| row.get(0))
+            .optional()?;
+        Ok(value.as_deref() == Some("true"))
+    }
+
+    pub fn database_key(db_path: &Path) -> Result<Option<String>, AppError> {
+        if let Some(key) = DB_KEY.read().unwrap().clone() {
+            return Ok(key);
+        }
+        let key = if is_encrypted(db_path)? {
+            match keyring_entry()?.get_password() {
+                Ok(key) => Some(key),
+                Err(keyring::Error::NoEntry) => {
+                    return Err(AppError::NotFound("Database is encrypted but its key is missing from the keychain".to_string()))
+                }
+                Err(e) => return Err(keyring_error(e)),
+            }
+        } else {
+            None
+        };
+        *DB_KEY.write().unwrap() = Some(key.clone());
+        Ok(key)
+    }
+
+    // A random 256-bit secret; SQLCipher derives the actual page key from it
+    fn generate_secret() -> String {
+        format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+    }
+
+    fn sidecar_path(db_path: &Path, suffix: &str) -> std::path::PathBuf {
+        std::path::PathBuf::from(format!("{}{}", db_path.display(), suffix))
+    }
+
+    pub fn encrypt_database_file(app: &AppHandle) -> Result<(), AppError> {
+        let db_path = get_db_path(app).map_err(|e| AppError::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?;
+        if database_key(&db_path)?.is_some() {
+            return Err(AppError::Validation("Database is already encrypted".to_string()));
+        }
+        let encrypted_path = db_path.with_extension("db.encrypted");
+        let _ = std::fs::remove_file(&encrypted_path);
+        let secret = generate_secret();
+
+        let conn = open_connection(&db_path)?;
+        // Leaving WAL checkpoints everything into the main file and removes -wal/-shm. It needs
+        // the only connection to the database, so it fails while a scan or another command
+        // still holds one open instead of letting their writes land in the file being replaced.
+        let journal_mode: String = conn.query_row("PRAGMA journal_mode = DELETE", [], |row| row.get(0))?;
+        if !journal_mode.eq_ignore_ascii_case("delete") {
+            return Err(AppError::Validation("The database is in use; try again once other activity has finished".to_string()));
+        }
+        // Holds the lock taken by the export until this connection closes, so nothing can
+        // write between the export and the swap
+        conn.query_row("PRAGMA locking_mode = EXCLUSIVE", [], |_| Ok(()))?;
+        conn.execute("ATTACH DATABASE ? AS encrypted KEY ?", rusqlite::params![encrypted_path.to_string_lossy(), secret])?;
+        let exported = conn.query_row("SELECT sqlcipher_export('encrypted')", [], |_| Ok(()));
+        conn.execute("DETACH DATABASE encrypted", [])?;
+        let prepared = exported.and_then(|_| {
+            let encrypted = Connection::open(&encrypted_path)?;
+            encrypted.pragma_update(None, "key", &secret)?;
+            set_setting_value(&encrypted, ENCRYPTED_DB_KEY, "true")?;
+            encrypted.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))
+        });
+        let restore_wal = |conn: &Connection| {
+            let _ = conn.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()));
+        };
+        if let Err(e) = prepared {
+            restore_wal(&conn);
+            let _ = std::fs::remove_file(&encrypted_path);
+            return Err(e.into());
+        }
+
+        let entry = keyring_entry()?;
+        if let Err(e) = entry.set_password(&secret) {
+            restore_wal(&conn);
+            let _ = std::fs::remove_file(&encrypted_path);
+            return Err(keyring_error(e));
+        }
+        // Windows won't replace a file that is still open, so the lock is released just
+        // before the rename
+        drop(conn);
+        for suffix in ["-wal", "-shm"] {
+            let _ = std::fs::remove_file(sidecar_path(&db_path, suffix));
+        }
+        if let Err(e) = std::fs::rename(&encrypted_path, &db_path) {
+            let _ = entry.delete_password();
+            let _ = std::fs::remove_file(&encrypted_path);
+            if let Ok(conn) = Connection::open(&db_path) {
+                restore_wal(&conn);
+            }
+            return Err(e.into());
+        }
+        *DB_KEY.write().unwrap() = Some(Some(secret));
+        Ok(())
+    }
+}
+
+/// SQLCipher passphrase for app.db, or `None` while it is plaintext. Errors when the database
+/// is encrypted but the key can't be read from the keychain, rather than opening it without one.
+#[cfg(feature = "sqlcipher")]
+pub fn database_key(db_path: &Path) -> Result<Option<String>, AppError> {
+    sqlcipher::database_key(db_path)
+}
+
+#[cfg(not(feature = "sqlcipher"))]
+pub fn database_key(_db_path: &Path) -> Result<Option<String>, AppError> {
+    Ok(None)
+}
+
+/// Re-encrypts a plaintext app.db with a new keychain-stored secret. The `encrypted_db`
+/// setting is written into the encrypted copy, so it only exists once the swap has succeeded.
+#[cfg(feature = "sqlcipher")]
+pub fn encrypt_database_file(app: &AppHandle) -> Result<(), AppError> {
+    sqlcipher::encrypt_database_file(app)
+}
+
+#[cfg(not(feature = "sqlcipher"))]
+pub fn encrypt_database_file(_app: &AppHandle) -> Result<(), AppError> {
+    Err(AppError::Validation("This build does not include database encryption (the `sqlcipher` feature)".to_string()))
+}
+
+#[tauri::command]
+pub fn encrypt_database(app: AppHandle) -> Result<(), String> {
+    encrypt_database_file(&app).map_err(|e| e.to_string())
+}
@@ -0,0 +1,53 @@
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub enum AppError {
+    Database(rusqlite::Error),
+    Io(std::io::Error),
+    NotFound(String),
+    PlatformNotFound { platform_id: i64 },
+    ApiNotFound { api: String, extension_id: String },
+    Validation(String),
+    DuplicateGame { existing_id: i64 },
+    AlreadyRunning { pid: u32 },
+    UnsupportedPlatform { os: String, supported: Vec<String> },
+    IncompatibleVersion { required: String, current: String },
+    Timeout { operation: String, limit: Duration },
+    TooLarge { limit_bytes: u64 },
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::Database(e) => write!(f, "Database error: {}", e),
+            AppError::Io(e) => write!(f, "IO error: {}", e),
+            AppError::NotFound(msg) => write!(f, "Not found: {}", msg),
+            AppError::PlatformNotFound { platform_id } => write!(f, "Not found: Platform {} not found", platform_id),
+            AppError::ApiNotFound { api, extension_id } => write!(f, "Not found: API {} is not provided by extension {}", api, extension_id),
+            AppError::Validation(msg) => write!(f, "Validation error: {}", msg),
+            AppError::DuplicateGame { existing_id } => write!(f, "Duplicate game: already exists with id {}", existing_id),
+            AppError::AlreadyRunning { pid } => write!(f, "Already running: process {}", pid),
+            AppError::UnsupportedPlatform { os, supported } => write!(f, "Unsupported platform: {} (supported: {})", os, supported.join(", ")),
+            AppError::IncompatibleVersion { required, current } => {
+                write!(f, "Incompatible version: requires app version {} or newer, but this is {}", required, current)
+            }
+            AppError::Timeout { operation, limit } => write!(f, "Timeout: {} did not finish within {:?}", operation, limit),
+            AppError::TooLarge { limit_bytes } => write!(f, "Too large: exceeds the {} byte limit", limit_bytes),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(e: rusqlite::Error) -> Self {
+        AppError::Database(e)
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        AppError::Io(e)
+    }
+}
@@ -23,22 +23,313 @@ pub struct FrontendStoreExtension {
     pub rating: f32,
     pub tags: Vec<String>,
 }
-use rusqlite::Connection;
+use crate::database::{get_connection, get_db_path, get_setting_value, open_connection, ensure_platform, query_games, upsert_source_game};
+use crate::models::{Game, GameFilter, SourceGame};
+use crate::error::AppError;
+use rusqlite::{Connection, OptionalExtension};
 use serde::Deserialize;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tauri::{AppHandle, Manager};
+use std::time::Duration;
+use tauri::AppHandle;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
 
 
+// Total size of the files under `path`; symlinks are counted but not followed
+fn directory_size(path: &Path) -> u64 {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| entries.filter_map(Result::ok).map(|entry| directory_size(&entry.path())).sum())
+        .unwrap_or(0)
+}
+
+// Optional manifest sections the framework's ExtensionManifest doesn't model are read from the raw file
+fn read_manifest_json(manifest_path: &Path) -> Result<Value, ExtensionError> {
+    let content = std::fs::read_to_string(manifest_path).map_err(ExtensionError::Io)?;
+    serde_json::from_str(&content).map_err(|e| ExtensionError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
+}
+
+// Refuses extensions whose `platforms` list excludes this OS; an empty or absent list means all
+fn check_supported_platform(manifest_path: &Path) -> Result<(), ExtensionError> {
+    let raw = read_manifest_json(manifest_path)?;
+    let platforms = raw.get("platforms").and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default();
+    let os = std::env::consts::OS;
+    if platforms.is_empty() || platforms.iter().filter_map(Value::as_str).any(|platform| platform.eq_ignore_ascii_case(os)) {
+        return Ok(());
+    }
+    let supported = platforms.iter().filter_map(Value::as_str).map(str::to_string).collect();
+    Err(app_error_to_extension(AppError::UnsupportedPlatform { os: os.to_string(), supported }))
+}
+
+// Reads a list of strings at `pointer` in the serialized manifest, e.g. "/apis/provided"
+fn manifest_string_list(manifest: &ExtensionManifest, pointer: &str) -> Vec<String> {
+    let Ok(manifest) = serde_json::to_value(manifest) else {
+        return Vec::new();
+    };
+    manifest
+        .pointer(pointer)
+        .and_then(Value::as_array)
+        .map(|items| items.iter().filter_map(|item| item.as_str().map(str::to_string)).collect())
+        .unwrap_or_default()
+}
+
+/// Hooks the app or its frontend calls. A manifest listing anything else still loads, but
+/// gets a warning, since a misspelt hook (`on_statup`) would otherwise silently never fire.
+/// Add new hook names here when something starts calling them.
+pub const KNOWN_HOOKS: &[&str] = &[
+    "on_startup",
+    "on_game_scan",
+    "on_game_added",
+    "on_game_updated",
+    "on_game_deleted",
+    "on_incremental_scan",
+    "get_library_filters",
+    "get_games",
+];
+
+fn unknown_hook_warnings(manifest: &ExtensionManifest) -> Vec<String> {
+    manifest_string_list(manifest, "/hooks")
+        .into_iter()
+        .filter(|hook| !KNOWN_HOOKS.contains(&hook.as_str()))
+        .map(|hook| format!("Unknown hook {} will never be called; known hooks are {}", hook, KNOWN_HOOKS.join(", ")))
+        .collect()
+}
+
+#[derive(Serialize)]
+pub struct ManifestValidation {
+    pub valid: bool,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// JSON Schema (draft-07) for extension manifest files, for editor validation.
+/// ExtensionManifest comes from the framework crate, so the schema is kept by hand here;
+/// a test checks it against the manifest's serialized field names.
+pub fn manifest_json_schema() -> Value {
+    let string_list = serde_json::json!({ "type": "array", "items": { "type": "string" } });
+    serde_json::json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "Arcadia extension manifest",
+        "type": "object",
+        "required": ["name", "version", "type", "entryPoint", "permissions"],
+        "properties": {
+            "name": { "type": "string", "minLength": 1 },
+            "version": { "type": "string", "description": "Semantic version, e.g. 1.0.0" },
+            "author": { "type": "string" },
+            "description": { "type": "string" },
+            "type": { "enum": ["Theme", "DataSource", "GameLibrary"] },
+            "entryPoint": { "type": "string", "minLength": 1 },
+            "permissions": string_list,
+            "dependencies": { "type": "object", "additionalProperties": { "type": "string" } },
+            "hooks": string_list,
+            "apis": {
+                "type": "object",
+                "properties": { "provided": string_list, "required": string_list }
+            },
+            "menuItems": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["title", "url"],
+                    "properties": {
+                        "title": { "type": "string" },
+                        "url": { "type": "string" },
+                        "icon": { "type": "string" },
+                        "order": { "type": "integer", "description": "Position in the menu; lower comes first, unset goes last" },
+                        "items": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "required": ["title", "url"],
+                                "properties": { "title": { "type": "string" }, "url": { "type": "string" } }
+                            }
+                        }
+                    }
+                }
+            },
+            "min_app_version": { "type": "string", "description": "Oldest app version the extension supports" },
+            "platforms": {
+                "type": "array",
+                "items": { "enum": ["windows", "macos", "linux"] },
+                "description": "Operating systems the extension runs on; empty or absent means all"
+            },
+            "default_settings": { "type": "object", "additionalProperties": { "type": "string" } },
+            "config_schema": {
+                "type": "array",
+                "description": "Settings the app renders as a form",
+                "items": {
+                    "type": "object",
+                    "required": ["key", "type"],
+                    "properties": {
+                        "key": { "type": "string", "minLength": 1 },
+                        "type": { "enum": CONFIG_FIELD_TYPES },
+                        "label": { "type": "string" },
+                        "default": {},
+                        "options": { "type": "array", "description": "Choices for a select field" }
+                    }
+                }
+            },
+            "call_timeout_ms": { "type": "integer", "minimum": 1, "description": "Per-call limit for hooks and APIs" }
+        }
+    })
+}
+
+#[tauri::command]
+pub fn get_manifest_json_schema() -> Value {
+    manifest_json_schema()
+}
+
+#[derive(Serialize)]
+pub struct HookResult {
+    pub extension_id: String,
+    pub result: Result<Value, String>,
+}
+
+/// A library filter contributed by an extension through the `get_library_filters` hook.
+/// Applying it calls the extension's `predicate_api` with the candidate game ids.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LibraryFilter {
+    #[serde(default)]
+    pub extension_id: String,
+    pub id: String,
+    pub label: String,
+    pub predicate_api: String,
+}
+
+/// Setting types a `config_schema` field may use; each maps to one form control.
+pub const CONFIG_FIELD_TYPES: &[&str] = &["string", "boolean", "integer", "number", "select"];
+
+/// One setting described by a manifest's optional `config_schema`, for generated settings forms.
+/// `options` lists the choices of a `select` field.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ConfigField {
+    pub key: String,
+    #[serde(rename = "type")]
+    pub field_type: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub default: Option<Value>,
+    #[serde(default)]
+    pub options: Vec<Value>,
+}
+
+// Parses and checks `config_schema`; an absent schema is empty
+fn manifest_config_schema(raw: &Value) -> Result<Vec<ConfigField>, ExtensionError> {
+    let invalid = |message: String| ExtensionError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, format!("Invalid config_schema: {}", message)));
+    let Some(schema) = raw.get("config_schema") else {
+        return Ok(Vec::new());
+    };
+    let fields: Vec<ConfigField> = serde_json::from_value(schema.clone()).map_err(|e| invalid(e.to_string()))?;
+    let mut keys = std::collections::HashSet::new();
+    for field in &fields {
+        if !CONFIG_FIELD_TYPES.contains(&field.field_type.as_str()) {
+            return Err(invalid(format!("{} has unsupported type {}, expected one of {}", field.key, field.field_type, CONFIG_FIELD_TYPES.join(", "))));
+        }
+        if field.field_type == "select" && field.options.is_empty() {
+            return Err(invalid(format!("select field {} has no options", field.key)));
+        }
+        if !keys.insert(field.key.as_str()) {
+            return Err(invalid(format!("{} is declared more than once", field.key)));
+        }
+    }
+    Ok(fields)
+}
+
+/// An installed extension together with the store source and listing it was installed from.
+pub struct StoreInstall {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub source_id: String,
+    pub store_extension_id: String,
+}
+
 pub struct ExtensionManager {
     extensions: HashMap<String, Box<dyn ExtensionImpl>>,
     registry: ExtensionRegistry,
     context: ExtensionContext,
+    // APIs each extension declares under `apis.provided`, keyed by extension id
+    provided_apis: HashMap<String, Vec<String>>,
+    // Hooks each loaded extension subscribes to via the manifest's `hooks` list
+    subscribed_hooks: HashMap<String, Vec<String>>,
+    // Why a registered extension failed to load during restore, keyed by extension id
+    init_errors: HashMap<String, String>,
+    // Per-extension overrides of DEFAULT_CALL_TIMEOUT from the manifest's `call_timeout_ms`
+    call_timeouts: HashMap<String, Duration>,
+    // Run order for hooks and menu items: lower runs first, missing means 0
+    priorities: HashMap<String, i32>,
+    // Each menu item's `order` from the raw manifest, by position in `menuItems`
+    menu_orders: HashMap<String, Vec<Option<i32>>>,
+}
+
+// How long a single hook or API call may run before it's abandoned
+const DEFAULT_CALL_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn manifest_call_timeout(manifest_path: &Path) -> Option<Duration> {
+    let raw = read_manifest_json(manifest_path).ok()?;
+    raw.get("call_timeout_ms").and_then(Value::as_u64).map(Duration::from_millis)
+}
+
+// MenuItem has no `order` field, so it's read per item from the raw manifest
+fn manifest_menu_orders(manifest_path: &Path) -> Vec<Option<i32>> {
+    let Ok(raw) = read_manifest_json(manifest_path) else {
+        return Vec::new();
+    };
+    raw.get("menuItems")
+        .and_then(Value::as_array)
+        .map(|items| items.iter().map(|item| item.get("order").and_then(Value::as_i64).and_then(|o| i32::try_from(o).ok())).collect())
+        .unwrap_or_default()
+}
+
+// Items as (order, extension name, item): set orders first, ascending, then by extension name.
+// The sort is stable, so remaining ties keep the order they were collected in.
+fn sort_menu_items<T>(mut items: Vec<(Option<i32>, &String, T)>) -> Vec<T> {
+    items.sort_by(|a, b| (a.0.is_none(), a.0, a.1).cmp(&(b.0.is_none(), b.0, b.1)));
+    items.into_iter().map(|(_, _, item)| item).collect()
+}
+
+#[derive(Serialize)]
+pub struct ExtensionStatus {
+    pub registered: bool,
+    pub loaded: bool,
+    pub enabled: bool,
+    pub init_error: Option<String>,
+}
+
+#[derive(Serialize, Default)]
+pub struct ExtensionCounts {
+    pub total: usize,
+    pub enabled: usize,
+    pub disabled: usize,
+}
+
+#[derive(Serialize, Default)]
+pub struct ExtensionSummary {
+    pub all: ExtensionCounts,
+    pub by_type: std::collections::BTreeMap<String, ExtensionCounts>,
+}
+
+impl ExtensionCounts {
+    fn add(&mut self, enabled: bool) {
+        self.total += 1;
+        if enabled {
+            self.enabled += 1;
+        } else {
+            self.disabled += 1;
+        }
+    }
 }
 
 impl ExtensionManager {
@@ -50,6 +341,12 @@ impl ExtensionManager {
                 app_handle,
                 extension_dir,
             },
+            provided_apis: HashMap::new(),
+            subscribed_hooks: HashMap::new(),
+            init_errors: HashMap::new(),
+            call_timeouts: HashMap::new(),
+            priorities: HashMap::new(),
+            menu_orders: HashMap::new(),
         }
     }
 
@@ -59,6 +356,9 @@ impl ExtensionManager {
 
         // Validate manifest
         self.validate_manifest(&manifest)?;
+        self.check_min_app_version(manifest_path)?;
+        check_supported_platform(manifest_path)?;
+        manifest_config_schema(&read_manifest_json(manifest_path)?)?;
 
         // Generate unique ID
         let id = Uuid::new_v4().to_string();
@@ -86,32 +386,339 @@ impl ExtensionManager {
             enabled: true,
         });
 
+        // Register the APIs it declares
+        self.provided_apis.insert(id.clone(), manifest_string_list(extension.get_manifest(), "/apis/provided"));
+        self.subscribed_hooks.insert(id.clone(), manifest_string_list(extension.get_manifest(), "/hooks"));
+        if let Some(timeout) = manifest_call_timeout(manifest_path) {
+            self.call_timeouts.insert(id.clone(), timeout);
+        }
+        self.menu_orders.insert(id.clone(), manifest_menu_orders(manifest_path));
+
         // Store extension
         self.extensions.insert(id.clone(), extension);
 
         Ok(id)
     }
 
+    /// Reloads the extensions recorded in the database, keeping their ids and enabled state.
+    /// One that fails to load stays registered with its error recorded, so it can still be
+    /// inspected and uninstalled. Returns how many were loaded.
+    pub async fn restore_extensions(&mut self) -> Result<usize, ExtensionError> {
+        let rows: Vec<(String, String, String, Option<String>, Option<String>, String, String, bool, i32)> = {
+            let conn = self.get_db_connection()?;
+            let mut stmt = conn.prepare("SELECT id, name, version, author, description, type, manifest_path, enabled, priority FROM extensions")?;
+            let rows = stmt.query_map([], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?, row.get(6)?, row.get(7)?, row.get(8)?))
+            })?;
+            rows.collect::<Result<_, _>>()?
+        };
+
+        let mut loaded = 0;
+        for (id, name, version, author, description, extension_type, manifest_path, enabled, priority) in rows {
+            self.priorities.insert(id.clone(), priority);
+            self.registry.register(ExtensionInfo {
+                id: id.clone(),
+                name,
+                version,
+                author: author.filter(|a| !a.is_empty()),
+                description: description.filter(|d| !d.is_empty()),
+                extension_type,
+                enabled,
+            });
+            match self.restore_extension(&id, Path::new(&manifest_path)).await {
+                Ok(()) => loaded += 1,
+                Err(e) => {
+                    println!("Failed to restore extension {}: {}", id, e);
+                    self.init_errors.insert(id, e.to_string());
+                }
+            }
+        }
+        Ok(loaded)
+    }
+
+    async fn restore_extension(&mut self, id: &str, manifest_path: &Path) -> Result<(), ExtensionError> {
+        let manifest = self.parse_manifest(manifest_path)?;
+        self.validate_manifest(&manifest)?;
+        self.check_min_app_version(manifest_path)?;
+        check_supported_platform(manifest_path)?;
+        manifest_config_schema(&read_manifest_json(manifest_path)?)?;
+        let dir = manifest_path.parent().map(Path::to_path_buf).unwrap_or_default();
+        let mut extension = self.create_extension(id, manifest, dir)?;
+        extension.initialize(&self.context).await?;
+        self.provided_apis.insert(id.to_string(), manifest_string_list(extension.get_manifest(), "/apis/provided"));
+        self.subscribed_hooks.insert(id.to_string(), manifest_string_list(extension.get_manifest(), "/hooks"));
+        if let Some(timeout) = manifest_call_timeout(manifest_path) {
+            self.call_timeouts.insert(id.to_string(), timeout);
+        }
+        self.menu_orders.insert(id.to_string(), manifest_menu_orders(manifest_path));
+        self.extensions.insert(id.to_string(), extension);
+        Ok(())
+    }
+
+    /// The manifest an extension was loaded with, or the one on disk if it's registered but not loaded.
+    pub fn get_extension_manifest(&self, id: &str) -> Result<Value, ExtensionError> {
+        let manifest = match self.extensions.get(id) {
+            Some(extension) => serde_json::to_value(extension.get_manifest()),
+            None => serde_json::to_value(self.parse_manifest(&self.get_manifest_path(id)?)?),
+        };
+        manifest.map_err(|e| ExtensionError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
+    }
+
+    pub fn get_extension_status(&self, id: &str) -> ExtensionStatus {
+        let info = self.registry.get_all().into_iter().find(|ext| ext.id == id);
+        ExtensionStatus {
+            registered: info.is_some(),
+            loaded: self.extensions.contains_key(id),
+            enabled: info.is_some_and(|ext| ext.enabled),
+            init_error: self.init_errors.get(id).cloned(),
+        }
+    }
+
+    /// Registered extensions counted overall and per `extension_type`, split by enabled state.
+    pub fn get_extension_summary(&self) -> ExtensionSummary {
+        let mut summary = ExtensionSummary::default();
+        for info in self.registry.get_all() {
+            summary.all.add(info.enabled);
+            summary.by_type.entry(info.extension_type).or_default().add(info.enabled);
+        }
+        summary
+    }
+
     pub async fn unload_extension(&mut self, id: &str) -> Result<(), ExtensionError> {
         if let Some(mut extension) = self.extensions.remove(id) {
             extension.shutdown().await?;
-            self.registry.unregister(id);
-            self.remove_extension_from_db(id).await?;
+        } else if !self.init_errors.contains_key(id) {
+            return Ok(());
+        }
+        // Extensions that failed to restore are registered but not loaded; uninstall them too
+        self.registry.unregister(id);
+        self.provided_apis.remove(id);
+        self.subscribed_hooks.remove(id);
+        self.init_errors.remove(id);
+        self.call_timeouts.remove(id);
+        self.priorities.remove(id);
+        self.menu_orders.remove(id);
+        self.remove_extension_from_db(id).await?;
+        Ok(())
+    }
+
+    /// Unloads an extension and, unless `keep_files` is set, deletes its install directory.
+    /// Returns the bytes freed on disk.
+    pub async fn uninstall_extension(&mut self, id: &str, keep_files: bool) -> Result<u64, ExtensionError> {
+        // Look the directory up first; unloading removes the row that records it
+        let install_dir = if keep_files { None } else { self.get_extension_dir(id).ok() };
+        self.unload_extension(id).await?;
+        match install_dir {
+            Some(dir) => self.remove_extension_files(&dir),
+            None => Ok(0),
+        }
+    }
+
+    // Only directories strictly inside the managed extensions dir are deleted, so a manifest_path
+    // pointing elsewhere (e.g. a crafted or sideloaded manifest) never removes arbitrary files
+    fn remove_extension_files(&self, dir: &Path) -> Result<u64, ExtensionError> {
+        let (Ok(dir), Ok(root)) = (dir.canonicalize(), self.context.extension_dir.canonicalize()) else {
+            return Ok(0);
+        };
+        if dir == root || !dir.starts_with(&root) {
+            println!("Not deleting {}: outside the extensions directory {}", dir.display(), root.display());
+            return Ok(0);
+        }
+        let freed = directory_size(&dir);
+        std::fs::remove_dir_all(&dir).map_err(ExtensionError::Io)?;
+        Ok(freed)
+    }
+
+    /// Calls `shutdown` on every loaded extension, giving each at most `timeout`. Failures are
+    /// logged and skipped so one misbehaving extension can't stop the rest from shutting down.
+    pub async fn shutdown_all(&mut self, timeout: Duration) {
+        for (id, extension) in self.extensions.iter_mut() {
+            match tokio::time::timeout(timeout, extension.shutdown()).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => println!("Extension {} failed to shut down: {}", id, e),
+                Err(_) => println!("Extension {} timed out while shutting down", id),
+            }
+        }
+    }
+
+    /// Loaded extensions in run order: ascending priority, then id so ties are deterministic.
+    fn ordered_extensions(&self) -> Vec<&Box<dyn ExtensionImpl>> {
+        let mut extensions: Vec<&Box<dyn ExtensionImpl>> = self.extensions.values().collect();
+        extensions.sort_by_key(|extension| (self.priorities.get(extension.get_id()).copied().unwrap_or(0), extension.get_id().to_string()));
+        extensions
+    }
+
+    pub async fn set_extension_priority(&mut self, id: &str, priority: i32) -> Result<(), ExtensionError> {
+        let conn = self.get_db_connection()?;
+        let affected = conn.execute("UPDATE extensions SET priority = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?", rusqlite::params![priority, id])?;
+        if affected == 0 {
+            return Err(ExtensionError::NotFound(format!("Extension {} not found", id)));
         }
+        self.priorities.insert(id.to_string(), priority);
         Ok(())
     }
 
+    /// Runs one hook or API call, abandoning it with a timeout error if the extension
+    /// doesn't answer within its call timeout.
+    async fn dispatch(&self, extension: &dyn ExtensionImpl, hook: &str, params: Value) -> Result<Value, ExtensionError> {
+        let id = extension.get_id();
+        let limit = self.call_timeouts.get(id).copied().unwrap_or(DEFAULT_CALL_TIMEOUT);
+        match tokio::time::timeout(limit, extension.handle_hook(hook, params)).await {
+            Ok(result) => result,
+            Err(_) => Err(app_error_to_extension(AppError::Timeout { operation: format!("extension {} running {}", id, hook), limit })),
+        }
+    }
+
     #[allow(unused)]
     pub async fn call_hook(&self, hook: &str, params: Value) -> Result<Vec<Value>, ExtensionError> {
         let mut results = Vec::new();
-        for extension in self.extensions.values() {
-            if let Ok(result) = extension.handle_hook(hook, params.clone()).await {
+        for extension in self.ordered_extensions() {
+            if let Ok(result) = self.dispatch(extension.as_ref(), hook, params.clone()).await {
                 results.push(result);
             }
         }
         Ok(results)
     }
 
+    fn enabled_ids(&self) -> std::collections::HashSet<String> {
+        self.registry.get_enabled().into_iter().map(|e| e.id).collect()
+    }
+
+    /// The first non-null result from enabled extensions in run order; errors are logged and skipped.
+    pub async fn call_hook_first(&self, hook: &str, params: Value) -> Option<Value> {
+        let enabled_ids = self.enabled_ids();
+        for extension in self.ordered_extensions() {
+            if !enabled_ids.contains(extension.get_id()) {
+                continue;
+            }
+            match self.dispatch(extension.as_ref(), hook, params.clone()).await {
+                Ok(Value::Null) => {}
+                Ok(result) => return Some(result),
+                Err(e) => println!("Extension {} failed handling {}: {}", extension.get_id(), hook, e),
+            }
+        }
+        None
+    }
+
+    /// Shallow-merges the object results of enabled extensions into one object. When two
+    /// extensions return the same key, the one that runs first wins. Non-object results are ignored.
+    pub async fn call_hook_merged(&self, hook: &str, params: Value) -> serde_json::Map<String, Value> {
+        let enabled_ids = self.enabled_ids();
+        let mut merged = serde_json::Map::new();
+        for extension in self.ordered_extensions() {
+            if !enabled_ids.contains(extension.get_id()) {
+                continue;
+            }
+            match self.dispatch(extension.as_ref(), hook, params.clone()).await {
+                Ok(Value::Object(result)) => {
+                    for (key, value) in result {
+                        merged.entry(key).or_insert(value);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => println!("Extension {} failed handling {}: {}", extension.get_id(), hook, e),
+            }
+        }
+        merged
+    }
+
+    /// Like `call_hook`, but keeps each result (or error) attributed to the extension that produced it.
+    pub async fn call_hook_detailed(&self, hook: &str, params: Value) -> Vec<HookResult> {
+        let enabled_ids = self.enabled_ids();
+        let mut results = Vec::new();
+        for extension in self.ordered_extensions() {
+            if !enabled_ids.contains(extension.get_id()) {
+                continue;
+            }
+            let result = self.dispatch(extension.as_ref(), hook, params.clone()).await.map_err(|e| e.to_string());
+            results.push(HookResult {
+                extension_id: extension.get_id().to_string(),
+                result,
+            });
+        }
+        results
+    }
+
+    /// Calls `hook` on enabled extensions that list it in their manifest's `hooks`.
+    pub async fn notify_subscribers(&self, hook: &str, params: Value) -> Vec<HookResult> {
+        let enabled_ids = self.enabled_ids();
+        let mut results = Vec::new();
+        for extension in self.ordered_extensions() {
+            let id = extension.get_id();
+            if !enabled_ids.contains(id) || !self.subscribed_hooks.get(id).is_some_and(|hooks| hooks.iter().any(|h| h == hook)) {
+                continue;
+            }
+            let result = self.dispatch(extension.as_ref(), hook, params.clone()).await.map_err(|e| e.to_string());
+            results.push(HookResult {
+                extension_id: id.to_string(),
+                result,
+            });
+        }
+        results
+    }
+
+    /// Filters declared by enabled extensions subscribed to `get_library_filters`, each of which
+    /// answers with an array of `{ id, label, predicate_api }`. Malformed answers are logged and skipped.
+    pub async fn get_library_filters(&self) -> Vec<LibraryFilter> {
+        let mut filters = Vec::new();
+        for hook_result in self.notify_subscribers("get_library_filters", serde_json::json!({})).await {
+            let declared = hook_result.result.and_then(|value| serde_json::from_value::<Vec<LibraryFilter>>(value).map_err(|e| e.to_string()));
+            match declared {
+                Ok(declared) => filters.extend(declared.into_iter().map(|filter| LibraryFilter { extension_id: hook_result.extension_id.clone(), ..filter })),
+                Err(e) => println!("Extension {} returned no usable library filters: {}", hook_result.extension_id, e),
+            }
+        }
+        filters
+    }
+
+    /// Runs an extension's filter over `game_ids`. The predicate API gets `{ filter_id, game_ids }`
+    /// and answers with the ids that match; ids it didn't receive are ignored, and order is kept.
+    pub async fn apply_library_filter(&self, extension_id: &str, filter_id: &str, game_ids: Vec<i64>) -> Result<Vec<i64>, ExtensionError> {
+        let filter = self
+            .get_library_filters()
+            .await
+            .into_iter()
+            .find(|filter| filter.extension_id == extension_id && filter.id == filter_id)
+            .ok_or_else(|| ExtensionError::NotFound(format!("Library filter {} not found for extension {}", filter_id, extension_id)))?;
+        let params = serde_json::json!({ "filter_id": filter_id, "game_ids": game_ids });
+        let result = self.call_extension_api(extension_id, &filter.predicate_api, params).await?;
+        let matched: std::collections::HashSet<i64> =
+            serde_json::from_value(result).map_err(|e| ExtensionError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))?;
+        Ok(game_ids.into_iter().filter(|id| matched.contains(id)).collect())
+    }
+
+    /// Dispatches an API call, rejecting APIs the extension didn't declare in `apis.provided`
+    /// with `AppError::ApiNotFound` (inside `ExtensionError::Io`) before anything runs.
+    pub async fn call_extension_api(&self, id: &str, api: &str, params: Value) -> Result<Value, ExtensionError> {
+        let extension = self.extensions.get(id).ok_or_else(|| ExtensionError::NotFound(format!("Extension {} not found", id)))?;
+        if !self.provided_apis.get(id).is_some_and(|apis| apis.iter().any(|a| a == api)) {
+            return Err(app_error_to_extension(AppError::ApiNotFound { api: api.to_string(), extension_id: id.to_string() }));
+        }
+        self.dispatch(extension.as_ref(), api, params).await
+    }
+
+    pub fn list_extension_apis(&self, id: &str) -> Result<Vec<String>, ExtensionError> {
+        self.provided_apis
+            .get(id)
+            .cloned()
+            .ok_or_else(|| ExtensionError::NotFound(format!("Extension {} not found", id)))
+    }
+
+    /// Hook name to the ids of loaded extensions that declare it in their manifest's `hooks`.
+    pub fn get_extension_hooks(&self) -> std::collections::BTreeMap<String, Vec<String>> {
+        let mut hooks: std::collections::BTreeMap<String, Vec<String>> = std::collections::BTreeMap::new();
+        for (id, declared) in &self.subscribed_hooks {
+            for hook in declared {
+                hooks.entry(hook.clone()).or_default().push(id.clone());
+            }
+        }
+        for ids in hooks.values_mut() {
+            ids.sort();
+        }
+        hooks
+    }
+
+    #[allow(unused)]
     pub fn get_extension(&self, id: &str) -> Option<&Box<dyn ExtensionImpl>> {
         self.extensions.get(id)
     }
@@ -120,17 +727,28 @@ impl ExtensionManager {
         self.registry.get_all()
     }
 
+    pub fn list_extensions_by_type(&self, extension_type: &ExtensionType) -> Vec<ExtensionInfo> {
+        let extension_type = extension_type.to_string();
+        self.registry.get_all().into_iter().filter(|ext| ext.extension_type == extension_type).collect()
+    }
+
+    /// Menu items of enabled extensions, sorted by each item's manifest `order` (unset last),
+    /// then by extension name. Remaining ties keep extension priority and manifest order.
     pub fn get_extension_menu_items(&self) -> Vec<MenuItem> {
         let mut all_menu_items = Vec::new();
-        let enabled_ids: std::collections::HashSet<String> = self.registry.get_enabled().into_iter().map(|e| e.id).collect();
-        for extension in self.extensions.values() {
+        let enabled_ids = self.enabled_ids();
+        for extension in self.ordered_extensions() {
             if enabled_ids.contains(extension.get_id()) {
                 if let Some(menu_items) = &extension.get_manifest().menu_items {
-                    all_menu_items.extend(menu_items.clone());
+                    let orders = self.menu_orders.get(extension.get_id());
+                    for (index, item) in menu_items.iter().enumerate() {
+                        let order = orders.and_then(|orders| orders.get(index).copied().flatten());
+                        all_menu_items.push((order, &extension.get_manifest().name, item.clone()));
+                    }
                 }
             }
         }
-        all_menu_items
+        sort_menu_items(all_menu_items)
     }
 
     pub async fn enable_extension(&mut self, id: &str) -> Result<(), ExtensionError> {
@@ -153,10 +771,131 @@ impl ExtensionManager {
         }
     }
 
+    pub async fn get_last_scanned(&self, id: &str) -> Result<Option<String>, ExtensionError> {
+        let conn = self.get_db_connection()?;
+        let last_scanned = conn
+            .query_row("SELECT last_scanned FROM extensions WHERE id = ?", [id], |row| row.get::<_, Option<String>>(0))
+            .optional()?;
+        match last_scanned {
+            Some(value) => Ok(value),
+            None => Err(ExtensionError::NotFound(format!("Extension {} not found", id))),
+        }
+    }
+
+    pub async fn set_last_scanned(&self, id: &str, scanned_at: &str) -> Result<(), ExtensionError> {
+        let conn = self.get_db_connection()?;
+        conn.execute("UPDATE extensions SET last_scanned = ? WHERE id = ?", [scanned_at, id])?;
+        Ok(())
+    }
+
+    /// Records the store source and listing an installed extension came from.
+    pub async fn set_store_origin(&self, id: &str, source_id: &str, store_extension_id: &str) -> Result<(), ExtensionError> {
+        let conn = self.get_db_connection()?;
+        conn.execute("UPDATE extensions SET store_source_id = ?, store_extension_id = ? WHERE id = ?", [source_id, store_extension_id, id])?;
+        Ok(())
+    }
+
+    /// Installed extensions that came from a store source.
+    pub fn store_installs(&self) -> Result<Vec<StoreInstall>, ExtensionError> {
+        let conn = self.get_db_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, version, store_source_id, store_extension_id FROM extensions
+             WHERE store_source_id IS NOT NULL AND store_extension_id IS NOT NULL",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(StoreInstall { id: row.get(0)?, name: row.get(1)?, version: row.get(2)?, source_id: row.get(3)?, store_extension_id: row.get(4)? })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// The app capabilities available to a loaded, enabled extension.
+    pub fn host(&self, id: &str) -> Result<ExtensionHost, ExtensionError> {
+        if !self.extensions.contains_key(id) {
+            return Err(ExtensionError::NotFound(format!("Extension {} not found", id)));
+        }
+        if !self.enabled_ids().contains(id) {
+            return Err(ExtensionError::Io(std::io::Error::new(std::io::ErrorKind::PermissionDenied, format!("Extension {} is disabled", id))));
+        }
+        Ok(ExtensionHost::new(id, self.context.app_handle.clone()))
+    }
+
+    /// Asks a game-library extension for games added, changed, or removed since its last scan.
+    /// The hook receives `{ "since": <RFC3339 timestamp or null> }`; on success the scan start
+    /// time becomes the new `last_scanned`, so changes made during the scan are picked up next time.
+    pub async fn run_incremental_scan(&self, id: &str) -> Result<Value, ExtensionError> {
+        let extension = self.extensions.get(id).ok_or_else(|| ExtensionError::NotFound(format!("Extension {} not found", id)))?;
+        if !matches!(extension.get_type(), ExtensionType::GameLibrary) {
+            return Err(ExtensionError::NotFound(format!("Extension {} is not a game library", id)));
+        }
+        let since = self.get_last_scanned(id).await?;
+        let scan_started = chrono::Utc::now().to_rfc3339();
+        let result = self.dispatch(extension.as_ref(), "on_incremental_scan", serde_json::json!({ "since": since })).await?;
+        self.set_last_scanned(id, &scan_started).await?;
+        Ok(result)
+    }
+
+    /// Pulls a game-library extension's games into the library. The `get_games` hook returns
+    /// `{ "platform": <name>, "games": [SourceGame] }`; games are upserted by their external id
+    /// under that platform (created if missing), using the extension's own declared permissions.
+    /// Returns the ids of the stored games.
+    pub async fn sync_extension_games(&self, id: &str) -> Result<Vec<i64>, ExtensionError> {
+        let extension = self.extensions.get(id).ok_or_else(|| ExtensionError::NotFound(format!("Extension {} not found", id)))?;
+        if !matches!(extension.get_type(), ExtensionType::GameLibrary) {
+            return Err(ExtensionError::NotFound(format!("Extension {} is not a game library", id)));
+        }
+        let host = self.host(id)?;
+        let result = self.dispatch(extension.as_ref(), "get_games", serde_json::json!({})).await?;
+        let synced: SyncedGames = serde_json::from_value(result).map_err(|e| {
+            ExtensionError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, format!("Extension {} returned unusable games: {}", id, e)))
+        })?;
+        host.store_games(&synced.platform, &synced.games)
+    }
+
+    /// The directory an installed extension was loaded from (its manifest's parent).
+    fn get_manifest_path(&self, id: &str) -> Result<PathBuf, ExtensionError> {
+        let conn = self.get_db_connection()?;
+        let manifest_path: String = conn
+            .query_row("SELECT manifest_path FROM extensions WHERE id = ?", [id], |row| row.get(0))
+            .optional()?
+            .ok_or_else(|| ExtensionError::NotFound(format!("Extension {} not found", id)))?;
+        Ok(PathBuf::from(manifest_path))
+    }
+
+    /// Settings declared under the manifest's optional `default_settings` object.
+    /// Non-string values are stored in their JSON form.
+    pub fn get_default_settings(&self, id: &str) -> Result<std::collections::BTreeMap<String, String>, ExtensionError> {
+        let raw = read_manifest_json(&self.get_manifest_path(id)?)?;
+        let defaults = raw.get("default_settings").and_then(Value::as_object);
+        Ok(defaults
+            .map(|defaults| {
+                defaults
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// The settings form declared by the manifest's optional `config_schema`, empty if there is none.
+    pub fn get_config_schema(&self, id: &str) -> Result<Vec<ConfigField>, ExtensionError> {
+        manifest_config_schema(&read_manifest_json(&self.get_manifest_path(id)?)?)
+    }
+
+    /// Where extensions are installed, as resolved at startup.
+    pub fn extensions_dir(&self) -> &Path {
+        &self.context.extension_dir
+    }
+
+    pub fn get_extension_dir(&self, id: &str) -> Result<PathBuf, ExtensionError> {
+        self.get_manifest_path(id)?
+            .parent()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| ExtensionError::NotFound(format!("Extension {} has no install directory", id)))
+    }
+
     fn get_db_connection(&self) -> Result<Connection, ExtensionError> {
-        let data_dir = self.context.app_handle.path().app_data_dir().map_err(|e| ExtensionError::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?;
-        let db_path = data_dir.join("app.db");
-        Connection::open(db_path).map_err(ExtensionError::Database)
+        let db_path = get_db_path(&self.context.app_handle).map_err(|e| ExtensionError::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?;
+        open_connection(&db_path).map_err(ExtensionError::Database)
     }
 
     fn parse_manifest(&self, manifest_path: &Path) -> Result<ExtensionManifest, ExtensionError> {
@@ -164,7 +903,45 @@ impl ExtensionManager {
     }
 
     fn validate_manifest(&self, manifest: &ExtensionManifest) -> Result<(), ExtensionError> {
-        manifest::validate_manifest(manifest)
+        manifest::validate_manifest(manifest)?;
+        for warning in unknown_hook_warnings(manifest) {
+            println!("Warning: extension {}: {}", manifest.name, warning);
+        }
+        Ok(())
+    }
+
+    /// Runs the checks a load would, without loading anything. Errors would stop the load;
+    /// warnings (such as hook names nothing calls) would not.
+    pub fn validate_manifest_file(&self, manifest_path: &Path) -> ManifestValidation {
+        let manifest = match self.parse_manifest(manifest_path) {
+            Ok(manifest) => manifest,
+            Err(e) => return ManifestValidation { valid: false, errors: vec![e.to_string()], warnings: Vec::new() },
+        };
+        let checks = [
+            manifest::validate_manifest(&manifest),
+            self.check_min_app_version(manifest_path),
+            check_supported_platform(manifest_path),
+            read_manifest_json(manifest_path).and_then(|raw| manifest_config_schema(&raw)).map(|_| ()),
+        ];
+        let errors: Vec<String> = checks.into_iter().filter_map(Result::err).map(|e| e.to_string()).collect();
+        ManifestValidation { valid: errors.is_empty(), errors, warnings: unknown_hook_warnings(&manifest) }
+    }
+
+    // `min_app_version` is optional, so it's read from the raw manifest rather than ExtensionManifest
+    fn check_min_app_version(&self, manifest_path: &Path) -> Result<(), ExtensionError> {
+        let raw = read_manifest_json(manifest_path)?;
+        let Some(required) = raw.get("min_app_version").and_then(Value::as_str) else {
+            return Ok(());
+        };
+        let required_version = semver::Version::parse(required).map_err(|e| {
+            ExtensionError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, format!("Invalid min_app_version {}: {}", required, e)))
+        })?;
+        let current = self.context.app_handle.package_info().version.to_string();
+        let current_version = semver::Version::parse(&current).map_err(|e| ExtensionError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))?;
+        if current_version < required_version {
+            return Err(app_error_to_extension(AppError::IncompatibleVersion { required: required.to_string(), current }));
+        }
+        Ok(())
     }
 
     fn create_extension(&self, id: &str, manifest: ExtensionManifest, path: PathBuf) -> Result<Box<dyn ExtensionImpl>, ExtensionError> {
@@ -212,6 +989,7 @@ impl ExtensionManager {
         let conn = self.get_db_connection()?;
         conn.execute("DELETE FROM extension_permissions WHERE extension_id = ?", [id])?;
         conn.execute("DELETE FROM extension_settings WHERE extension_id = ?", [id])?;
+        conn.execute("DELETE FROM extension_storage WHERE extension_id = ?", [id])?;
         conn.execute("DELETE FROM extensions WHERE id = ?", [id])?;
         Ok(())
     }
@@ -223,6 +1001,168 @@ impl ExtensionManager {
     }
 }
 
+pub const PERMISSION_PLATFORMS_WRITE: &str = "db:platforms:write";
+pub const PERMISSION_GAMES_READ: &str = "db:games:read";
+pub const PERMISSION_GAMES_WRITE: &str = "db:games:write";
+
+fn app_error_to_extension(e: AppError) -> ExtensionError {
+    match e {
+        AppError::Database(e) => ExtensionError::Database(e),
+        AppError::Io(e) => ExtensionError::Io(e),
+        AppError::NotFound(msg) => ExtensionError::NotFound(msg),
+        // The rest travel inside Io, where callers can still downcast them back to AppError
+        other @ (AppError::UnsupportedPlatform { .. } | AppError::IncompatibleVersion { .. }) => ExtensionError::Io(std::io::Error::new(std::io::ErrorKind::Unsupported, other)),
+        other @ (AppError::PlatformNotFound { .. } | AppError::ApiNotFound { .. }) => ExtensionError::Io(std::io::Error::new(std::io::ErrorKind::NotFound, other)),
+        other @ AppError::Timeout { .. } => ExtensionError::Io(std::io::Error::new(std::io::ErrorKind::TimedOut, other)),
+        other => ExtensionError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, other)),
+    }
+}
+
+#[derive(Deserialize)]
+struct SyncedGames {
+    platform: String,
+    games: Vec<SourceGame>,
+}
+
+/// App capabilities for one loaded extension. The extension id is fixed when the host is
+/// created, and permissions are looked up from what that extension's installed manifest
+/// declared (recorded in `extension_permissions` at load), never from anything the caller passes.
+#[derive(Clone)]
+pub struct ExtensionHost {
+    extension_id: String,
+    app_handle: AppHandle,
+}
+
+impl ExtensionHost {
+    pub fn new(extension_id: &str, app_handle: AppHandle) -> Self {
+        ExtensionHost { extension_id: extension_id.to_string(), app_handle }
+    }
+
+    fn connection(&self) -> Result<Connection, ExtensionError> {
+        let db_path = get_db_path(&self.app_handle).map_err(|e| ExtensionError::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?;
+        open_connection(&db_path).map_err(ExtensionError::Database)
+    }
+
+    fn require_permission(&self, conn: &Connection, permission: &str) -> Result<(), ExtensionError> {
+        let declared = conn
+            .query_row(
+                "SELECT 1 FROM extension_permissions WHERE extension_id = ? AND permission = ?",
+                [self.extension_id.as_str(), permission],
+                |_| Ok(()),
+            )
+            .optional()?;
+        if declared.is_some() {
+            Ok(())
+        } else {
+            Err(ExtensionError::Io(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                format!("Extension {} requires the '{}' permission", self.extension_id, permission),
+            )))
+        }
+    }
+
+    /// Ensures a platform named `name` exists and returns its id. Safe to call on every
+    /// `initialize`: an existing platform is returned as-is rather than violating its UNIQUE name.
+    pub fn register_platform(&self, name: &str, icon_path: Option<&str>) -> Result<i64, ExtensionError> {
+        let conn = self.connection()?;
+        self.require_permission(&conn, PERMISSION_PLATFORMS_WRITE)?;
+        Ok(ensure_platform(&conn, name, icon_path)?)
+    }
+
+    /// Read-only snapshot of games matching `filter`. Extensions get copies, never the connection.
+    pub fn query_games(&self, filter: &GameFilter) -> Result<Vec<Game>, ExtensionError> {
+        let conn = self.connection()?;
+        self.require_permission(&conn, PERMISSION_GAMES_READ)?;
+        conn.execute_batch("PRAGMA query_only = ON;").map_err(ExtensionError::Database)?;
+        query_games(&conn, filter).map_err(ExtensionError::Database)
+    }
+
+    /// Adds or updates the games this extension reports, under a platform named `platform_name`
+    /// (created if missing). Needs both the platform and games write permissions.
+    pub fn store_games(&self, platform_name: &str, games: &[SourceGame]) -> Result<Vec<i64>, ExtensionError> {
+        let conn = self.connection()?;
+        self.require_permission(&conn, PERMISSION_GAMES_WRITE)?;
+        let platform_id = self.register_platform(platform_name, None)?;
+        games.iter().map(|game| upsert_source_game(&conn, &self.extension_id, platform_id, game).map_err(app_error_to_extension)).collect()
+    }
+}
+
+// Applied to every store fetch so a source test behaves exactly like a real browse
+const STORE_FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub const HTTP_PROXY_KEY: &str = "http_proxy";
+pub const NO_PROXY_KEY: &str = "no_proxy";
+
+#[derive(Clone)]
+struct ProxyConfig {
+    url: String,
+    // Comma-separated hosts to bypass, as in NO_PROXY; falls back to the NO_PROXY env var
+    no_proxy: Option<String>,
+}
+
+// Read from settings at startup and whenever they change, so clients can be built without a connection
+static STORE_PROXY: std::sync::OnceLock<std::sync::RwLock<Option<ProxyConfig>>> = std::sync::OnceLock::new();
+
+fn store_proxy() -> Option<ProxyConfig> {
+    STORE_PROXY.get_or_init(Default::default).read().unwrap().clone()
+}
+
+/// Reloads the `http_proxy` and `no_proxy` settings used for store requests.
+/// An empty `http_proxy` turns the proxy off.
+pub fn load_store_proxy(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let setting = |key| get_setting_value(conn, key).map(|value| value.filter(|value| !value.trim().is_empty()));
+    let config = match setting(HTTP_PROXY_KEY)? {
+        Some(url) => Some(ProxyConfig { url: url.trim().to_string(), no_proxy: setting(NO_PROXY_KEY)? }),
+        None => None,
+    };
+    *STORE_PROXY.get_or_init(Default::default).write().unwrap() = config;
+    Ok(())
+}
+
+// http(s):// and socks5(h):// proxies are both accepted by reqwest::Proxy::all
+fn proxied_client_builder() -> Result<reqwest::ClientBuilder, String> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(config) = store_proxy() {
+        let no_proxy = config.no_proxy.as_deref().and_then(reqwest::NoProxy::from_string).or_else(reqwest::NoProxy::from_env);
+        let proxy = reqwest::Proxy::all(&config.url).map_err(|e| format!("ProxyError: invalid proxy {}: {}", config.url, e))?;
+        builder = builder.proxy(proxy.no_proxy(no_proxy));
+    }
+    Ok(builder)
+}
+
+// Connection failures while a proxy is configured are reported as proxy errors so users know where to look
+fn describe_request_error(action: &str, e: &reqwest::Error) -> String {
+    match store_proxy() {
+        Some(config) if e.is_connect() => format!("ProxyError: {} through proxy {} failed: {}", action, config.url, e),
+        _ => format!("Failed to {}: {}", action, e),
+    }
+}
+
+fn store_http_client() -> Result<reqwest::Client, String> {
+    proxied_client_builder()?
+        .timeout(STORE_FETCH_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
+
+// ExtensionStoreClient is handed the app's client so REST store calls honour the proxy settings
+// and timeout like every other store fetch
+fn store_client() -> Result<ExtensionStoreClient, String> {
+    Ok(ExtensionStoreClient::with_client(store_http_client()?))
+}
+
+// Finds the reqwest error under a store client failure so proxy connect errors are reported as such
+fn describe_store_error(action: &str, e: &ExtensionError) -> String {
+    let mut source = std::error::Error::source(e);
+    while let Some(inner) = source {
+        if let Some(request_error) = inner.downcast_ref::<reqwest::Error>() {
+            return describe_request_error(action, request_error);
+        }
+        source = inner.source();
+    }
+    format!("Failed to {}: {}", action, e)
+}
+
 #[derive(Deserialize)]
 struct DefaultExtension {
     name: String,
@@ -233,6 +1173,270 @@ struct DefaultExtension {
     tags: Vec<String>,
     icon: Option<String>,
     manifest_url: String,
+    // Where the installable archive lives; entries without one can be browsed but not installed
+    #[serde(default)]
+    package_url: String,
+    #[serde(default)]
+    checksum: String,
+}
+
+const MAX_LISTING_BYTES: usize = 4 * 1024 * 1024;
+
+/// How a store source's listing is fetched and parsed. Supporting a new store format means
+/// adding a variant and its arm in `fetch_extensions`; the commands dispatch through here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StoreFormat {
+    /// A single JSON file listing every extension, like the Arcadia store manifest
+    #[serde(alias = "default", alias = "arcadia_manifest")]
+    ArcadiaManifest,
+    /// A store server queried through ExtensionStoreClient
+    #[serde(alias = "rest_api")]
+    RestApi,
+}
+
+impl StoreFormat {
+    // Fallback for sources without a recognised source_type: a `.json` file is a manifest,
+    // anything else is a store API
+    pub fn for_url(base_url: &str) -> Self {
+        let path = url::Url::parse(base_url).map(|url| url.path().to_string()).unwrap_or_else(|_| base_url.to_string());
+        if path.to_ascii_lowercase().ends_with(".json") {
+            StoreFormat::ArcadiaManifest
+        } else {
+            StoreFormat::RestApi
+        }
+    }
+
+    // StoreSource is a framework type, so its source_type is read from the serialized source.
+    // Values that don't name a format fall back to the URL.
+    pub fn for_source(source: &StoreSource) -> Self {
+        serde_json::to_value(source)
+            .ok()
+            .and_then(|source| source.get("source_type").or_else(|| source.get("type")).cloned())
+            .and_then(|source_type| serde_json::from_value(source_type).ok())
+            .unwrap_or_else(|| Self::for_url(&source.base_url))
+    }
+
+    async fn fetch_extensions(
+        self,
+        source: &StoreSource,
+        filters: &FrontendStoreFilters,
+        sort: &SortOption,
+        page: u32,
+        limit: u32,
+        manifest_cache: &ManifestCache,
+    ) -> Result<Vec<FrontendStoreExtension>, String> {
+        match self {
+            StoreFormat::ArcadiaManifest => {
+                let manifest_content = fetch_manifest_cached(&source.base_url, manifest_cache).await?;
+                let exts: Vec<DefaultExtension> = serde_json::from_str(&manifest_content).map_err(|e| format!("Failed to parse manifest: {}", e))?;
+                Ok(exts.into_iter().map(|ext| FrontendStoreExtension {
+                    id: ext.manifest_url.clone(),
+                    name: ext.name,
+                    description: ext.description,
+                    version: ext.version,
+                    author: ext.author,
+                    extension_type: ExtensionType::GameLibrary,
+                    source_id: source.id.clone(),
+                    icon: ext.icon,
+                    download_count: 0,
+                    rating: 0.0,
+                    tags: ext.tags,
+                }).collect())
+            }
+            StoreFormat::RestApi => {
+                let client = store_client()?;
+                let api_filters = StoreFilters {
+                    extension_type: filters.extension_type.clone(),
+                    tags: filters.tags.clone(),
+                    search: filters.search.clone(),
+                };
+                let exts = tokio::time::timeout(STORE_FETCH_TIMEOUT, client.fetch_extensions(&source.base_url, &api_filters, sort, page, limit))
+                    .await
+                    .map_err(|_| format!("Timed out fetching extensions from source {}", source.id))?
+                    .map_err(|e| describe_store_error("fetch extensions", &e))?;
+                Ok(exts.into_iter().map(|ext| FrontendStoreExtension {
+                    id: ext.id,
+                    name: ext.name,
+                    description: ext.description,
+                    version: ext.version,
+                    author: ext.author,
+                    extension_type: ext.extension_type,
+                    source_id: source.id.clone(),
+                    icon: None, // External sources don't provide icons
+                    download_count: ext.download_count,
+                    rating: ext.rating,
+                    tags: ext.tags,
+                }).collect())
+            }
+        }
+    }
+}
+
+async fn get_enabled_source(store_manager: &Arc<RwLock<StoreManager>>, source_id: &str) -> Result<StoreSource, String> {
+    let manager = store_manager.read().await;
+    let source = manager.get_source(source_id).ok_or_else(|| format!("Source {} not found", source_id))?;
+    if !source.enabled {
+        return Err(format!("Source {} is disabled", source_id));
+    }
+    Ok(source.clone())
+}
+
+#[derive(Clone)]
+struct CachedManifest {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: String,
+}
+
+/// Last successful manifest response per URL, revalidated with conditional requests.
+#[derive(Default)]
+pub struct ManifestCache {
+    entries: std::sync::Mutex<HashMap<String, CachedManifest>>,
+}
+
+impl ManifestCache {
+    fn get(&self, url: &str) -> Option<CachedManifest> {
+        self.entries.lock().unwrap().get(url).cloned()
+    }
+
+    fn insert(&self, url: &str, entry: CachedManifest) {
+        self.entries.lock().unwrap().insert(url.to_string(), entry);
+    }
+}
+
+// Sends If-None-Match/If-Modified-Since from the cached response and reuses its body on 304
+async fn fetch_manifest_cached(url: &str, cache: &ManifestCache) -> Result<String, String> {
+    let client = store_http_client()?;
+    let cached = cache.get(url);
+    let mut request = client.get(url);
+    if let Some(cached) = &cached {
+        if let Some(etag) = &cached.etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag.as_str());
+        }
+        if let Some(last_modified) = &cached.last_modified {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified.as_str());
+        }
+    }
+    let response = request.send().await.map_err(|e| describe_request_error("download manifest", &e))?;
+
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        println!("Manifest {} not modified, using cached copy", url);
+        return cached.map(|c| c.body).ok_or_else(|| format!("Source returned 304 for {} but nothing is cached", url));
+    }
+
+    let header = |name: reqwest::header::HeaderName| response.headers().get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+    let etag = header(reqwest::header::ETAG);
+    let last_modified = header(reqwest::header::LAST_MODIFIED);
+    let is_success = response.status().is_success();
+    let body = response.text().await.map_err(|e| format!("Failed to read response: {}", e))?;
+    if is_success && (etag.is_some() || last_modified.is_some()) {
+        cache.insert(url, CachedManifest { etag, last_modified, body: body.clone() });
+    }
+    Ok(body)
+}
+
+const MAX_README_BYTES: usize = 1024 * 1024;
+
+// Reads at most `max_bytes` of the body, failing early on an oversized Content-Length
+async fn download_text_capped(url: &str, max_bytes: usize) -> Result<String, String> {
+    let client = store_http_client()?;
+    let mut response = client.get(url).send().await.map_err(|e| describe_request_error(&format!("download {}", url), &e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to download {}: status {}", url, response.status()));
+    }
+    if response.content_length().is_some_and(|len| len as usize > max_bytes) {
+        return Err(format!("{} exceeds the {} byte limit", url, max_bytes));
+    }
+    let mut data = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| format!("Failed to download {}: {}", url, e))? {
+        if data.len() + chunk.len() > max_bytes {
+            return Err(format!("{} exceeds the {} byte limit", url, max_bytes));
+        }
+        data.extend_from_slice(&chunk);
+    }
+    String::from_utf8(data).map_err(|e| format!("{} is not valid UTF-8: {}", url, e))
+}
+
+/// JSON object mapping a store source id to extra hosts allowed to serve its manifests,
+/// e.g. `{"my-source": ["cdn.example.com"]}`. A source's own host is always allowed.
+pub const STORE_MANIFEST_HOSTS_KEY: &str = "store_manifest_hosts";
+
+// Loaded like the proxy settings so manifest URLs can be checked without a connection
+static STORE_MANIFEST_HOSTS: std::sync::OnceLock<std::sync::RwLock<HashMap<String, Vec<String>>>> = std::sync::OnceLock::new();
+
+pub fn parse_store_manifest_hosts(value: &str) -> Result<HashMap<String, Vec<String>>, AppError> {
+    if value.trim().is_empty() {
+        return Ok(HashMap::new());
+    }
+    serde_json::from_str(value).map_err(|e| AppError::Validation(format!("{} must map source ids to lists of hosts: {}", STORE_MANIFEST_HOSTS_KEY, e)))
+}
+
+/// Reloads the `store_manifest_hosts` setting used by `validate_manifest_url`.
+pub fn load_store_manifest_hosts(conn: &Connection) -> Result<(), AppError> {
+    let hosts = parse_store_manifest_hosts(&get_setting_value(conn, STORE_MANIFEST_HOSTS_KEY)?.unwrap_or_default())?;
+    *STORE_MANIFEST_HOSTS.get_or_init(Default::default).write().unwrap() = hosts;
+    Ok(())
+}
+
+/// Checks a store source URL and puts it in canonical form: http(s) only, with a host,
+/// and without trailing slashes on the path, so equivalent URLs fetch and compare the same.
+fn normalize_store_url(base_url: &str) -> Result<String, AppError> {
+    let base_url = base_url.trim();
+    let mut url = url::Url::parse(base_url).map_err(|e| match e {
+        url::ParseError::RelativeUrlWithoutBase => AppError::Validation(format!("Store URL {} must start with http:// or https://", base_url)),
+        e => AppError::Validation(format!("Invalid store URL {}: {}", base_url, e)),
+    })?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(AppError::Validation(format!("Store URL {} must use http or https", base_url)));
+    }
+    if url.host_str().unwrap_or_default().is_empty() {
+        return Err(AppError::Validation(format!("Store URL {} has no host", base_url)));
+    }
+    let path = url.path().trim_end_matches('/').to_string();
+    url.set_path(&path);
+    let normalized = url.to_string();
+    // The url crate always keeps a "/" root path, so drop it when nothing follows
+    if url.query().is_none() && url.fragment().is_none() {
+        Ok(normalized.trim_end_matches('/').to_string())
+    } else {
+        Ok(normalized)
+    }
+}
+
+/// Rejects manifest and package URLs that point away from the store source they were listed by,
+/// so a crafted store entry can't redirect installs to an arbitrary host.
+fn validate_manifest_url(source: &StoreSource, manifest_url: &str) -> Result<(), AppError> {
+    let manifest = url::Url::parse(manifest_url).map_err(|e| AppError::Validation(format!("Invalid URL {}: {}", manifest_url, e)))?;
+    if !matches!(manifest.scheme(), "http" | "https") {
+        return Err(AppError::Validation(format!("URL {} must use http or https", manifest_url)));
+    }
+    let manifest_host = manifest.host_str().unwrap_or_default();
+    let source_host = url::Url::parse(&source.base_url).ok().and_then(|url| url.host_str().map(str::to_string));
+    let trusted = STORE_MANIFEST_HOSTS
+        .get_or_init(Default::default)
+        .read()
+        .unwrap()
+        .get(&source.id)
+        .is_some_and(|hosts| hosts.iter().any(|host| host.eq_ignore_ascii_case(manifest_host)));
+    if source_host.as_deref() == Some(manifest_host) || trusted {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!("URL {} is not hosted by store source {}", manifest_url, source.id)))
+    }
+}
+
+// README.md is expected to sit next to the extension's manifest
+fn readme_url_for_manifest(manifest_url: &str) -> Result<String, String> {
+    let manifest_url = url::Url::parse(manifest_url).map_err(|e| format!("Invalid manifest URL {}: {}", manifest_url, e))?;
+    let readme_url = manifest_url.join("README.md").map_err(|e| e.to_string())?;
+    Ok(readme_url.to_string())
+}
+
+#[derive(Serialize)]
+pub struct StoreSourceTestResult {
+    pub reachable: bool,
+    pub extension_count: Option<u32>,
+    pub error: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -257,6 +1461,7 @@ pub async fn fetch_store_extensions(
     page: u32,
     limit: u32,
     store_manager: tauri::State<'_, Arc<RwLock<StoreManager>>>,
+    manifest_cache: tauri::State<'_, ManifestCache>,
 ) -> Result<Vec<FrontendStoreExtension>, String> {
     println!("fetch_store_extensions called with page: {}, limit: {}", page, limit);
     println!("Filters: {:?}", filters);
@@ -276,53 +1481,10 @@ pub async fn fetch_store_extensions(
                 continue;
             }
 
-            if source_id == "default" {
-                // For the default source, load extensions directly from the JSON file
-                println!("Loading extensions from default source JSON file");
-                let response = reqwest::get(&source.base_url).await.map_err(|e| format!("Failed to download manifest: {}", e))?;
-                let manifest_content = response.text().await.map_err(|e| format!("Failed to read response: {}", e))?;
-                let default_exts: Vec<DefaultExtension> = serde_json::from_str(&manifest_content).map_err(|e| format!("Failed to parse manifest: {}", e))?;
-                println!("Parsed {} extensions from default source", default_exts.len());
-
-                let frontend_results: Vec<FrontendStoreExtension> = default_exts.into_iter().map(|ext| FrontendStoreExtension {
-                    id: ext.manifest_url.clone(),
-                    name: ext.name,
-                    description: ext.description,
-                    version: ext.version,
-                    author: ext.author,
-                    extension_type: ExtensionType::GameLibrary,
-                    source_id: source_id.clone(),
-                    icon: ext.icon,
-                    download_count: 0,
-                    rating: 0.0,
-                    tags: ext.tags,
-                }).collect();
-                results.extend(frontend_results);
-            } else {
-                // For other sources, use the API client
-                let client = ExtensionStoreClient::new();
-                let api_filters = StoreFilters {
-                    extension_type: filters.extension_type.clone(),
-                    tags: filters.tags.clone(),
-                    search: filters.search.clone(),
-                };
-                let source_results = client.fetch_extensions(&source.base_url, &api_filters, &sort, page, limit).await.map_err(|e| e.to_string())?;
-                println!("Fetched {} extensions from source {}", source_results.len(), source_id);
-                let frontend_results: Vec<FrontendStoreExtension> = source_results.into_iter().map(|ext| FrontendStoreExtension {
-                    id: ext.id,
-                    name: ext.name,
-                    description: ext.description,
-                    version: ext.version,
-                    author: ext.author,
-                    extension_type: ext.extension_type,
-                    source_id: source_id.clone(),
-                    icon: None, // External sources don't provide icons
-                    download_count: ext.download_count,
-                    rating: ext.rating,
-                    tags: ext.tags,
-                }).collect();
-                results.extend(frontend_results);
-            }
+            let format = StoreFormat::for_source(source);
+            let source_results = format.fetch_extensions(source, &filters, &sort, page, limit, &manifest_cache).await?;
+            println!("Fetched {} extensions from source {} ({:?})", source_results.len(), source_id, format);
+            results.extend(source_results);
         }
     } else {
         println!("No source_ids provided in filters");
@@ -338,93 +1500,369 @@ pub async fn fetch_extension_details(
     extension_id: String,
     store_manager: tauri::State<'_, Arc<RwLock<StoreManager>>>,
 ) -> Result<StoreExtensionDetails, String> {
-    if source_id == "default" {
-        // For default extensions, download the manifest from the extension_id (which is the manifest_url)
-        let client = ExtensionStoreClient::new();
-        let manifest: ExtensionManifest = client.download_manifest(&extension_id).await.map_err(|e| e.to_string())?;
-        let details = StoreExtensionDetails {
-            id: extension_id.clone(),
-            name: manifest.name,
-            version: manifest.version,
-            author: manifest.author.unwrap_or_default(),
-            description: manifest.description.unwrap_or_default(),
-            extension_type: manifest.extension_type,
-            download_count: 0,
-            rating: 0.0,
-            tags: vec![],
-            manifest_url: extension_id.clone(),
-            package_url: "".to_string(),
-            checksum: "".to_string(),
-            readme: "".to_string(),
-            screenshots: vec![],
-            dependencies: manifest.dependencies.unwrap_or_default(),
-        };
-        Ok(details)
+    let source = get_enabled_source(store_manager.inner(), &source_id).await?;
+    store_extension_details(&source, &extension_id).await
+}
+
+async fn store_extension_details(source: &StoreSource, extension_id: &str) -> Result<StoreExtensionDetails, String> {
+    let client = store_client()?;
+    match StoreFormat::for_source(source) {
+        StoreFormat::ArcadiaManifest => {
+            // Manifest-listed extensions are identified by their manifest URL
+            validate_manifest_url(source, extension_id).map_err(|e| e.to_string())?;
+            let manifest: ExtensionManifest = client.download_manifest(extension_id).await.map_err(|e| describe_store_error("download manifest", &e))?;
+            // The package isn't part of the extension manifest, so it comes from the listing entry
+            let listing = download_text_capped(&source.base_url, MAX_LISTING_BYTES).await?;
+            let entries: Vec<DefaultExtension> = serde_json::from_str(&listing).map_err(|e| format!("Failed to parse manifest: {}", e))?;
+            let (package_url, checksum) = entries
+                .into_iter()
+                .find(|entry| entry.manifest_url == extension_id)
+                .map(|entry| (entry.package_url, entry.checksum))
+                .unwrap_or_default();
+            let details = StoreExtensionDetails {
+                id: extension_id.to_string(),
+                name: manifest.name,
+                version: manifest.version,
+                author: manifest.author.unwrap_or_default(),
+                description: manifest.description.unwrap_or_default(),
+                extension_type: manifest.extension_type,
+                download_count: 0,
+                rating: 0.0,
+                tags: vec![],
+                manifest_url: extension_id.to_string(),
+                package_url,
+                checksum,
+                readme: "".to_string(),
+                screenshots: vec![],
+                dependencies: manifest.dependencies.unwrap_or_default(),
+            };
+            Ok(details)
+        }
+        StoreFormat::RestApi => client.fetch_extension_details(&source.base_url, extension_id).await.map_err(|e| describe_store_error("fetch extension details", &e)),
+    }
+}
+
+#[tauri::command]
+pub async fn fetch_extension_readme(
+    source_id: String,
+    extension_id: String,
+    store_manager: tauri::State<'_, Arc<RwLock<StoreManager>>>,
+) -> Result<String, String> {
+    let source = get_enabled_source(store_manager.inner(), &source_id).await?;
+    let readme_url = if StoreFormat::for_source(&source) == StoreFormat::ArcadiaManifest {
+        // Manifest-listed extensions are identified by their manifest URL
+        validate_manifest_url(&source, &extension_id).map_err(|e| e.to_string())?;
+        readme_url_for_manifest(&extension_id)?
     } else {
-        let manager = store_manager.inner().read().await;
-        let source = manager.get_source(&source_id).ok_or_else(|| format!("Source {} not found", source_id))?;
-        if !source.enabled {
-            return Err(format!("Source {} is disabled", source_id));
+        let client = store_client()?;
+        let details = client.fetch_extension_details(&source.base_url, &extension_id).await.map_err(|e| describe_store_error("fetch extension details", &e))?;
+        if details.readme.starts_with("http://") || details.readme.starts_with("https://") {
+            details.readme
+        } else if !details.readme.is_empty() {
+            return Ok(details.readme);
+        } else {
+            readme_url_for_manifest(&details.manifest_url)?
+        }
+    };
+    download_text_capped(&readme_url, MAX_README_BYTES).await
+}
+
+const INSTALL_CANCELLED: &str = "Installation cancelled";
+
+/// Cancellation flags for in-flight store installs, keyed by extension id.
+#[derive(Default)]
+pub struct InstallRegistry {
+    tokens: std::sync::Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl InstallRegistry {
+    fn begin(&self, extension_id: &str) -> Result<Arc<AtomicBool>, String> {
+        let mut tokens = self.tokens.lock().unwrap();
+        if tokens.contains_key(extension_id) {
+            return Err(format!("Extension {} is already being installed", extension_id));
+        }
+        let token = Arc::new(AtomicBool::new(false));
+        tokens.insert(extension_id.to_string(), token.clone());
+        Ok(token)
+    }
+
+    fn finish(&self, extension_id: &str) {
+        self.tokens.lock().unwrap().remove(extension_id);
+    }
+
+    pub fn cancel(&self, extension_id: &str) -> bool {
+        match self.tokens.lock().unwrap().get(extension_id) {
+            Some(token) => {
+                token.store(true, Ordering::SeqCst);
+                true
+            }
+            None => false,
         }
-        let client = ExtensionStoreClient::new();
-        client.fetch_extension_details(&source.base_url, &extension_id).await.map_err(|e| e.to_string())
+    }
+}
+
+fn check_cancelled(cancelled: &AtomicBool) -> Result<(), String> {
+    if cancelled.load(Ordering::SeqCst) {
+        return Err(INSTALL_CANCELLED.to_string());
+    }
+    Ok(())
+}
+
+// Streams the package so a cancellation is noticed between chunks rather than after the whole body
+pub const MAX_PACKAGE_BYTES_KEY: &str = "max_package_bytes";
+const DEFAULT_MAX_PACKAGE_BYTES: u64 = 100 * 1024 * 1024;
+
+// Hosts like raw.githubusercontent.com serve archives as octet-stream, so that is accepted too
+const PACKAGE_CONTENT_TYPES: &[&str] = &[
+    "application/zip",
+    "application/x-zip-compressed",
+    "application/gzip",
+    "application/x-gzip",
+    "application/octet-stream",
+];
+
+/// The package size limit, overridable through the `max_package_bytes` setting.
+fn max_package_bytes(app_handle: &AppHandle) -> u64 {
+    get_connection(app_handle)
+        .ok()
+        .and_then(|conn| get_setting_value(&conn, MAX_PACKAGE_BYTES_KEY).ok().flatten())
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_MAX_PACKAGE_BYTES)
+}
+
+// Unpacks a .zip or .tar.gz package into `extract_dir`, detected by its leading bytes rather than
+// the URL since sources don't name packages consistently. Both extractors refuse entries that
+// would land outside `extract_dir`.
+fn extract_package(package_path: &Path, extract_dir: &Path) -> Result<(), String> {
+    let mut file = std::fs::File::open(package_path).map_err(|e| format!("Failed to open package: {}", e))?;
+    let mut magic = [0u8; 4];
+    let read = file.read(&mut magic).map_err(|e| format!("Failed to read package: {}", e))?;
+    file.seek(SeekFrom::Start(0)).map_err(|e| format!("Failed to read package: {}", e))?;
+    match &magic[..read] {
+        [b'P', b'K', 3, 4] | [b'P', b'K', 5, 6] => {
+            let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("Invalid zip package: {}", e))?;
+            archive.extract(extract_dir).map_err(|e| format!("Failed to extract zip package: {}", e))
+        }
+        [0x1f, 0x8b, ..] => {
+            let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(file));
+            archive.unpack(extract_dir).map_err(|e| format!("Failed to extract tar.gz package: {}", e))
+        }
+        _ => Err("Unsupported package format: expected .zip or .tar.gz".to_string()),
+    }
+}
+
+const MAX_DOWNLOAD_ATTEMPTS: u32 = 3;
+
+enum DownloadFailure {
+    /// The connection dropped or the server hiccuped; worth another attempt
+    Interrupted(String),
+    Fatal(String),
+}
+
+// An unfinished download is kept here between attempts, and between installs, so it can be resumed
+fn partial_package_path(package_path: &Path) -> PathBuf {
+    package_path.with_extension("part")
+}
+
+// Downloads into the partial file, retrying interrupted transfers and resuming them with a Range
+// request where the server allows it. The package only lands at `package_path` once its checksum matches.
+async fn download_package(url: &str, checksum: &str, max_bytes: u64, cancelled: &AtomicBool, package_path: &Path) -> Result<(), String> {
+    let client = proxied_client_builder()?.build().map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+    let partial_path = partial_package_path(package_path);
+    if checksum.is_empty() {
+        // A resumed file can't be verified without a checksum, so never build on an old partial
+        let _ = std::fs::remove_file(&partial_path);
+    }
+    let mut attempt = 1;
+    loop {
+        match download_package_attempt(&client, url, max_bytes, cancelled, &partial_path).await {
+            Ok(()) => break,
+            Err(DownloadFailure::Interrupted(e)) if attempt < MAX_DOWNLOAD_ATTEMPTS => {
+                println!("Package download attempt {} of {} failed, retrying: {}", attempt, MAX_DOWNLOAD_ATTEMPTS, e);
+                attempt += 1;
+            }
+            Err(DownloadFailure::Interrupted(e) | DownloadFailure::Fatal(e)) => return Err(e),
+        }
+    }
+    if !checksum.is_empty() {
+        let data = std::fs::read(&partial_path).map_err(|e| format!("Failed to read package: {}", e))?;
+        let actual = format!("{:x}", md5::compute(&data));
+        if !actual.eq_ignore_ascii_case(checksum) {
+            let _ = std::fs::remove_file(&partial_path);
+            return Err(format!("Checksum mismatch: expected {}, got {}", checksum, actual));
+        }
+    }
+    std::fs::rename(&partial_path, package_path).map_err(|e| format!("Failed to save package: {}", e))
+}
+
+async fn download_package_attempt(
+    client: &reqwest::Client,
+    url: &str,
+    max_bytes: u64,
+    cancelled: &AtomicBool,
+    partial_path: &Path,
+) -> Result<(), DownloadFailure> {
+    let resume_from = std::fs::metadata(partial_path).map(|metadata| metadata.len()).unwrap_or(0);
+    // Packages are already compressed, and byte ranges must refer to the stored file rather than an encoding of it
+    let mut request = client.get(url).header(reqwest::header::ACCEPT_ENCODING, "identity");
+    if resume_from > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", resume_from));
+    }
+    let mut response = request.send().await.map_err(|e| DownloadFailure::Interrupted(describe_request_error("download package", &e)))?;
+    let status = response.status();
+    if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        // The partial no longer fits the package (it was replaced, or is already too long), so start over
+        let _ = std::fs::remove_file(partial_path);
+        return Err(DownloadFailure::Interrupted("Partial package no longer matches the source".to_string()));
+    }
+    if status.is_server_error() {
+        return Err(DownloadFailure::Interrupted(format!("Failed to download package: status {}", status)));
+    }
+    if !status.is_success() {
+        return Err(DownloadFailure::Fatal(format!("Failed to download package: status {}", status)));
+    }
+    // A response without a Content-Type is refused too; whatever passes here is still sniffed by extract_package
+    let content_type = response.headers().get(reqwest::header::CONTENT_TYPE).and_then(|value| value.to_str().ok()).unwrap_or_default();
+    let mime = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    if mime.is_empty() {
+        return Err(DownloadFailure::Fatal("Package response has no content type".to_string()));
+    }
+    if !PACKAGE_CONTENT_TYPES.contains(&mime.as_str()) {
+        return Err(DownloadFailure::Fatal(format!("Package has unexpected content type {}", content_type)));
+    }
+    // Only a 206 continues the partial; a server that ignores Range sends the whole body with a 200
+    let resuming = resume_from > 0 && status == reqwest::StatusCode::PARTIAL_CONTENT;
+    if resume_from > 0 && !resuming {
+        println!("Server ignored the range request for {}, downloading from the start", url);
+    }
+    let accepts_ranges = resuming
+        || response.headers().get(reqwest::header::ACCEPT_RANGES).and_then(|value| value.to_str().ok()).is_some_and(|value| value.eq_ignore_ascii_case("bytes"));
+    let mut written = if resuming { resume_from } else { 0 };
+    let too_large = || DownloadFailure::Fatal(AppError::TooLarge { limit_bytes: max_bytes }.to_string());
+    if response.content_length().is_some_and(|length| written + length > max_bytes) {
+        return Err(too_large());
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resuming)
+        .truncate(!resuming)
+        .open(partial_path)
+        .map_err(|e| DownloadFailure::Fatal(format!("Failed to write package: {}", e)))?;
+    loop {
+        let chunk = match response.chunk().await {
+            Ok(Some(chunk)) => chunk,
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                if !accepts_ranges {
+                    // Nothing to resume from next time, so don't leave the partial around
+                    let _ = std::fs::remove_file(partial_path);
+                }
+                return Err(DownloadFailure::Interrupted(format!("Failed to download package: {}", e)));
+            }
+        };
+        check_cancelled(cancelled).map_err(DownloadFailure::Fatal)?;
+        // Content-Length can be absent or wrong, so the streamed size is checked as well
+        if written + chunk.len() as u64 > max_bytes {
+            return Err(too_large());
+        }
+        file.write_all(&chunk).map_err(|e| DownloadFailure::Fatal(format!("Failed to write package: {}", e)))?;
+        written += chunk.len() as u64;
     }
 }
 
 #[tauri::command]
 pub async fn install_from_store(
+    app_handle: tauri::AppHandle,
     source_id: String,
     extension_id: String,
     extension_manager: tauri::State<'_, Arc<RwLock<ExtensionManager>>>,
     store_manager: tauri::State<'_, Arc<RwLock<StoreManager>>>,
+    installs: tauri::State<'_, InstallRegistry>,
 ) -> Result<String, String> {
-    let store_mgr = store_manager.inner().read().await;
-    let source = store_mgr.get_source(&source_id).ok_or_else(|| format!("Source {} not found", source_id))?;
-    if !source.enabled {
-        return Err(format!("Source {} is disabled", source_id));
-    }
-    let client = ExtensionStoreClient::new();
+    let cancelled = installs.begin(&extension_id)?;
+    // Manifest-listed extension ids are URLs, so the temp names are derived from a hash instead.
+    // The package has no extension either: it may be a zip or a tar.gz, which extract_package
+    // tells apart by its leading bytes. The name is stable so a partial download can be resumed.
+    let temp_dir = std::env::temp_dir();
+    let temp_name = format!("{:x}", md5::compute(format!("{}:{}", source_id, extension_id)));
+    let package_path = temp_dir.join(format!("arcadia-package-{}", temp_name));
+    let extract_dir = temp_dir.join(format!("arcadia-extracted-{}", temp_name));
 
-    // Fetch details
-    let details = client.fetch_extension_details(&source.base_url, &extension_id).await.map_err(|e| e.to_string())?;
+    let max_bytes = max_package_bytes(&app_handle);
+    let result = install_from_store_steps(&source_id, &extension_id, &package_path, &extract_dir, max_bytes, &cancelled, &extension_manager, &store_manager).await;
+    installs.finish(&extension_id);
 
-    // Download manifest
-    let manifest = client.download_manifest(&details.manifest_url).await.map_err(|e| e.to_string())?;
+    if let Err(e) = &result {
+        // Leave nothing half-written behind after a failed or cancelled install. An interrupted
+        // download's partial file is kept for the next attempt unless the user cancelled.
+        let _ = std::fs::remove_file(&package_path);
+        let _ = std::fs::remove_dir_all(&extract_dir);
+        if e == INSTALL_CANCELLED {
+            let _ = std::fs::remove_file(partial_package_path(&package_path));
+        }
+    }
+    result
+}
 
-    // Check if extension is already installed
-    let manager = extension_manager.inner().read().await;
-    let installed_extensions = manager.list_extensions();
-    let is_installed = installed_extensions.iter().any(|ext| ext.id == extension_id);
+async fn install_from_store_steps(
+    source_id: &str,
+    extension_id: &str,
+    package_path: &Path,
+    extract_dir: &Path,
+    max_bytes: u64,
+    cancelled: &AtomicBool,
+    extension_manager: &Arc<RwLock<ExtensionManager>>,
+    store_manager: &Arc<RwLock<StoreManager>>,
+) -> Result<String, String> {
+    let source = get_enabled_source(store_manager, source_id).await?;
+    let client = store_client()?;
 
-    // If installed, uninstall the old version first
-    let mut manager = extension_manager.inner().write().await;
-    if is_installed {
-        manager.unload_extension(&extension_id).await.map_err(|e| format!("Failed to uninstall old version: {}", e))?;
+    // Resolved per store format, the same way browsing shows them
+    let details = store_extension_details(&source, extension_id).await?;
+    if details.package_url.is_empty() {
+        return Err(format!("Store source {} lists no package for {}", source_id, extension_id));
     }
+    // Both downloads must come from the source's own host or one configured for it
+    validate_manifest_url(&source, &details.manifest_url).map_err(|e| e.to_string())?;
+    validate_manifest_url(&source, &details.package_url).map_err(|e| e.to_string())?;
 
-    // Download package
-    let package_data = client.download_extension(&details.package_url, &details.checksum).await.map_err(|e| e.to_string())?;
+    // Download manifest
+    let manifest = client.download_manifest(&details.manifest_url).await.map_err(|e| describe_store_error("download manifest", &e))?;
+    check_cancelled(cancelled)?;
 
-    // Save package to temp file
-    let temp_dir = std::env::temp_dir();
-    let package_path = temp_dir.join(format!("{}.zip", extension_id));
-    std::fs::write(&package_path, package_data).map_err(|e| e.to_string())?;
-
-    // Extract package (assuming it's a zip with manifest.json at root)
-    // For simplicity, assume the package contains the extension files directly
-    // In real implementation, extract to a temp dir and find manifest
-    let extract_dir = temp_dir.join(format!("extracted_{}", extension_id));
-    std::fs::create_dir_all(&extract_dir).map_err(|e| e.to_string())?;
-    // TODO: Implement zip extraction
-    // For now, assume manifest is downloaded separately
-
-    // Save manifest to extracted dir
+    // Download package to the temp file, resuming an earlier partial download if there is one
+    download_package(&details.package_url, &details.checksum, max_bytes, cancelled, package_path).await?;
+    check_cancelled(cancelled)?;
+
+    // Extract package; the extension files are expected at the archive root
+    std::fs::create_dir_all(extract_dir).map_err(|e| e.to_string())?;
+    extract_package(package_path, extract_dir)?;
+    check_cancelled(cancelled)?;
+
+    // Save the store's manifest to the extracted dir, replacing any packaged copy
     let manifest_path = extract_dir.join("manifest.json");
     let manifest_json = serde_json::to_string(&manifest).map_err(|e| e.to_string())?;
     std::fs::write(&manifest_path, manifest_json).map_err(|e| e.to_string())?;
+    check_cancelled(cancelled)?;
+
+    // If installed, uninstall the old version first. This is the last point a cancel is honored,
+    // so a cancelled install never removes the working version.
+    let mut manager = extension_manager.write().await;
+    let is_installed = manager.list_extensions().iter().any(|ext| ext.id == extension_id);
+    if is_installed {
+        manager.unload_extension(extension_id).await.map_err(|e| format!("Failed to uninstall old version: {}", e))?;
+    }
 
     // Install using ExtensionManager
-    manager.load_extension(&manifest_path).await.map_err(|e| e.to_string())
+    let id = manager.load_extension(&manifest_path).await.map_err(|e| e.to_string())?;
+    manager.set_store_origin(&id, source_id, extension_id).await.map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+#[tauri::command]
+pub fn cancel_install(extension_id: String, installs: tauri::State<'_, InstallRegistry>) -> bool {
+    installs.cancel(&extension_id)
 }
 
 #[tauri::command]
@@ -438,8 +1876,9 @@ pub async fn add_store_source(
     source: StoreSource,
     store_manager: tauri::State<'_, Arc<RwLock<StoreManager>>>,
 ) -> Result<(), String> {
+    let base_url = normalize_store_url(&source.base_url).map_err(|e| e.to_string())?;
     let mut manager = store_manager.inner().write().await;
-    manager.add_source(source).map_err(|e| e.to_string())
+    manager.add_source(StoreSource { base_url, ..source }).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -455,9 +1894,237 @@ pub async fn remove_store_source(
 pub async fn update_store_source(
     source: StoreSource,
     store_manager: tauri::State<'_, Arc<RwLock<StoreManager>>>,
+) -> Result<(), String> {
+    let base_url = normalize_store_url(&source.base_url).map_err(|e| e.to_string())?;
+    let mut manager = store_manager.inner().write().await;
+    manager.update_source(StoreSource { base_url, ..source }).map_err(|e| e.to_string())
+}
+
+/// Turns a source on or off without resending the whole source. Returns the new state.
+#[tauri::command]
+pub async fn set_store_source_enabled(
+    source_id: String,
+    enabled: bool,
+    store_manager: tauri::State<'_, Arc<RwLock<StoreManager>>>,
+) -> Result<bool, String> {
+    let mut manager = store_manager.inner().write().await;
+    let source = manager.get_source(&source_id).ok_or_else(|| AppError::NotFound(format!("Store source {}", source_id)).to_string())?.clone();
+    // Persisted through the store manager like every other source edit, so the two can't disagree
+    manager.update_source(StoreSource { enabled, ..source }).map_err(|e| e.to_string())?;
+    Ok(enabled)
+}
+
+/// Fetches a candidate source without persisting it. `source_type` picks the store format
+/// ("default" still means ArcadiaManifest); when omitted it is inferred from the URL.
+#[tauri::command]
+pub async fn test_store_source(url: String, source_type: Option<StoreFormat>) -> Result<StoreSourceTestResult, String> {
+    let failed = |reachable: bool, error: String| StoreSourceTestResult { reachable, extension_count: None, error: Some(error) };
+
+    if source_type.unwrap_or_else(|| StoreFormat::for_url(&url)) == StoreFormat::ArcadiaManifest {
+        let client = store_http_client()?;
+        let response = match client.get(&url).send().await {
+            Ok(response) => response,
+            Err(e) => return Ok(failed(false, describe_request_error("download manifest", &e))),
+        };
+        if !response.status().is_success() {
+            return Ok(failed(true, format!("Source responded with status {}", response.status())));
+        }
+        let manifest_content = match response.text().await {
+            Ok(content) => content,
+            Err(e) => return Ok(failed(true, format!("Failed to read response: {}", e))),
+        };
+        match serde_json::from_str::<Vec<DefaultExtension>>(&manifest_content) {
+            Ok(exts) => Ok(StoreSourceTestResult { reachable: true, extension_count: Some(exts.len() as u32), error: None }),
+            Err(e) => Ok(failed(true, format!("Failed to parse manifest: {}", e))),
+        }
+    } else {
+        let client = match store_client() {
+            Ok(client) => client,
+            Err(e) => return Ok(failed(false, e)),
+        };
+        let filters = StoreFilters { extension_type: None, tags: None, search: None };
+        match tokio::time::timeout(STORE_FETCH_TIMEOUT, client.fetch_extensions(&url, &filters, &SortOption::Name, 1, 100)).await {
+            Ok(Ok(exts)) => Ok(StoreSourceTestResult { reachable: true, extension_count: Some(exts.len() as u32), error: None }),
+            Ok(Err(e)) => Ok(failed(false, describe_store_error("fetch extensions", &e))),
+            Err(_) => Ok(failed(false, "Timed out fetching extensions".to_string())),
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct StoreSourceError {
+    pub source_id: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct StoreSummary {
+    pub total_extensions: u32,
+    pub by_type: HashMap<String, u32>,
+    pub source_counts: HashMap<String, u32>,
+    pub errors: Vec<StoreSourceError>,
+}
+
+// Re-opening the store page within this window reuses the last summary
+const STORE_SUMMARY_TTL: Duration = Duration::from_secs(60);
+// Summaries count the first page of each REST source, capped at this many entries
+const STORE_SUMMARY_LIMIT: u32 = 1000;
+
+#[derive(Default)]
+pub struct StoreSummaryCache {
+    entry: std::sync::Mutex<Option<(std::time::Instant, StoreSummary)>>,
+}
+
+/// Extension counts across every enabled source. A source that fails is listed in `errors`
+/// and left out of the counts.
+#[tauri::command]
+pub async fn get_store_summary(
+    store_manager: tauri::State<'_, Arc<RwLock<StoreManager>>>,
+    manifest_cache: tauri::State<'_, ManifestCache>,
+    summary_cache: tauri::State<'_, StoreSummaryCache>,
+) -> Result<StoreSummary, String> {
+    if let Some((fetched_at, summary)) = summary_cache.entry.lock().unwrap().as_ref() {
+        if fetched_at.elapsed() < STORE_SUMMARY_TTL {
+            return Ok(summary.clone());
+        }
+    }
+
+    let sources: Vec<StoreSource> = store_manager.inner().read().await.list_sources().into_iter().filter(|source| source.enabled).collect();
+    let filters = FrontendStoreFilters { extension_type: None, tags: None, search: None, source_ids: None };
+    let mut summary = StoreSummary::default();
+    for source in &sources {
+        let fetched = StoreFormat::for_source(source)
+            .fetch_extensions(source, &filters, &SortOption::Name, 1, STORE_SUMMARY_LIMIT, &manifest_cache)
+            .await;
+        match fetched {
+            Ok(extensions) => {
+                summary.total_extensions += extensions.len() as u32;
+                summary.source_counts.insert(source.id.clone(), extensions.len() as u32);
+                for extension in extensions {
+                    *summary.by_type.entry(extension.extension_type.to_string()).or_default() += 1;
+                }
+            }
+            Err(error) => summary.errors.push(StoreSourceError { source_id: source.id.clone(), error }),
+        }
+    }
+
+    *summary_cache.entry.lock().unwrap() = Some((std::time::Instant::now(), summary.clone()));
+    Ok(summary)
+}
+
+/// An installed extension whose store source lists a newer version.
+#[derive(Serialize, Clone)]
+pub struct ExtensionUpdate {
+    pub extension_id: String,
+    pub name: String,
+    pub installed_version: String,
+    pub available_version: String,
+    pub source_id: String,
+    pub store_extension_id: String,
+}
+
+#[derive(Serialize, Default)]
+pub struct AvailableUpdates {
+    pub updates: Vec<ExtensionUpdate>,
+    /// One entry per source that couldn't be checked
+    pub errors: Vec<StoreSourceError>,
+}
+
+fn push_source_error(errors: &mut Vec<StoreSourceError>, source_id: &str, error: String) {
+    if !errors.iter().any(|existing| existing.source_id == source_id) {
+        errors.push(StoreSourceError { source_id: source_id.to_string(), error });
+    }
+}
+
+// Versions that aren't semver can't be ordered, so they never count as an update
+fn is_newer_version(available: &str, installed: &str) -> bool {
+    match (semver::Version::parse(available), semver::Version::parse(installed)) {
+        (Ok(available), Ok(installed)) => available > installed,
+        _ => {
+            println!("Cannot compare versions {} and {}, assuming no update", available, installed);
+            false
+        }
+    }
+}
+
+/// Checks every extension installed from a store against its source, all at once, and returns
+/// the ones with a newer version. Extensions whose source is disabled are skipped.
+#[tauri::command]
+pub async fn get_all_available_updates(
+    extension_manager: tauri::State<'_, Arc<RwLock<ExtensionManager>>>,
+    store_manager: tauri::State<'_, Arc<RwLock<StoreManager>>>,
+) -> Result<AvailableUpdates, String> {
+    let installs = extension_manager.inner().read().await.store_installs().map_err(|e| e.to_string())?;
+    let mut result = AvailableUpdates::default();
+    let mut checks = tokio::task::JoinSet::new();
+    {
+        let manager = store_manager.inner().read().await;
+        for install in installs {
+            match manager.get_source(&install.source_id) {
+                Some(source) if source.enabled => {
+                    let source = source.clone();
+                    checks.spawn(async move {
+                        let details = store_extension_details(&source, &install.store_extension_id).await;
+                        (install, details)
+                    });
+                }
+                Some(_) => {}
+                None => push_source_error(&mut result.errors, &install.source_id, format!("Source {} not found", install.source_id)),
+            }
+        }
+    }
+    while let Some(checked) = checks.join_next().await {
+        let (install, details) = checked.map_err(|e| format!("Update check failed: {}", e))?;
+        match details {
+            Ok(details) if is_newer_version(&details.version, &install.version) => result.updates.push(ExtensionUpdate {
+                extension_id: install.id,
+                name: install.name,
+                installed_version: install.version,
+                available_version: details.version,
+                source_id: install.source_id,
+                store_extension_id: install.store_extension_id,
+            }),
+            Ok(_) => {}
+            Err(error) => push_source_error(&mut result.errors, &install.source_id, error),
+        }
+    }
+    // Checks finish in any order
+    result.updates.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn reorder_store_sources(
+    app_handle: tauri::AppHandle,
+    ordered_ids: Vec<String>,
+    store_manager: tauri::State<'_, Arc<RwLock<StoreManager>>>,
 ) -> Result<(), String> {
     let mut manager = store_manager.inner().write().await;
-    manager.update_source(source).map_err(|e| e.to_string())
+    for source_id in &ordered_ids {
+        if manager.get_source(source_id).is_none() {
+            return Err(format!("Source {} not found", source_id));
+        }
+    }
+
+    // Persist all priorities as one unit so a failure never leaves a half-reordered list
+    {
+        let mut conn = get_connection(&app_handle)?;
+        let tx = conn.transaction().map_err(|e| e.to_string())?;
+        for (index, source_id) in ordered_ids.iter().enumerate() {
+            tx.execute(
+                "UPDATE store_sources SET priority = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+                rusqlite::params![index as i64, source_id],
+            ).map_err(|e| e.to_string())?;
+        }
+        tx.commit().map_err(|e| e.to_string())?;
+    }
+
+    for source in manager.list_sources() {
+        if let Some(index) = ordered_ids.iter().position(|id| *id == source.id) {
+            manager.update_source(StoreSource { priority: index as i32, ..source }).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
 }
 
 
@@ -498,3 +2165,79 @@ impl ExtensionImpl for StubExtension {
         &self.id
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_manifest(dir: &Path, file_name: &str, manifest: Value) -> PathBuf {
+        let path = dir.join(file_name);
+        std::fs::write(&path, manifest.to_string()).unwrap();
+        path
+    }
+
+    #[test]
+    fn menu_items_sort_by_order_then_extension_name() {
+        let dir = std::env::temp_dir().join(format!("arcadia-menu-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let beta = write_manifest(&dir, "beta.json", serde_json::json!({ "menuItems": [{ "title": "Beta Home", "order": 1 }, { "title": "Beta Extra" }] }));
+        let alpha = write_manifest(&dir, "alpha.json", serde_json::json!({ "menuItems": [{ "title": "Alpha Extra" }, { "title": "Alpha Home", "order": 0 }] }));
+        let beta_orders = manifest_menu_orders(&beta);
+        let alpha_orders = manifest_menu_orders(&alpha);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let beta_name = "Beta".to_string();
+        let alpha_name = "Alpha".to_string();
+        let mut items = Vec::new();
+        for (index, title) in ["Beta Home", "Beta Extra"].into_iter().enumerate() {
+            items.push((beta_orders[index], &beta_name, title));
+        }
+        for (index, title) in ["Alpha Extra", "Alpha Home"].into_iter().enumerate() {
+            items.push((alpha_orders[index], &alpha_name, title));
+        }
+
+        assert_eq!(sort_menu_items(items), vec!["Alpha Home", "Beta Home", "Alpha Extra", "Beta Extra"]);
+    }
+
+    #[test]
+    fn manifest_schema_matches_serialized_manifest() {
+        let manifest: ExtensionManifest = serde_json::from_value(serde_json::json!({
+            "name": "Example",
+            "version": "1.0.0",
+            "author": "Author",
+            "description": "Description",
+            "type": "GameLibrary",
+            "entryPoint": "main.js",
+            "permissions": [PERMISSION_GAMES_READ],
+            "dependencies": { "other": "1.0.0" },
+            "hooks": ["get_games"],
+            "apis": { "provided": ["get_games"], "required": [] }
+        }))
+        .unwrap();
+        let serialized = serde_json::to_value(&manifest).unwrap();
+        let schema = manifest_json_schema();
+
+        let properties = schema["properties"].as_object().unwrap();
+        for key in serialized.as_object().unwrap().keys() {
+            assert!(properties.contains_key(key), "schema has no property for manifest field {}", key);
+        }
+        for required in schema["required"].as_array().unwrap() {
+            let key = required.as_str().unwrap();
+            assert!(serialized.get(key).is_some(), "schema requires {}, which the manifest doesn't serialize", key);
+        }
+        let api_properties = properties["apis"]["properties"].as_object().unwrap();
+        for key in serialized["apis"].as_object().unwrap().keys() {
+            assert!(api_properties.contains_key(key), "schema has no property for apis.{}", key);
+        }
+    }
+
+    #[test]
+    fn unknown_api_downcasts_to_api_not_found() {
+        let error = app_error_to_extension(AppError::ApiNotFound { api: "get_gmaes".to_string(), extension_id: "library".to_string() });
+
+        let ExtensionError::Io(io) = error else { panic!("expected ExtensionError::Io") };
+        assert_eq!(io.kind(), std::io::ErrorKind::NotFound);
+        let app_error = io.into_inner().unwrap().downcast::<AppError>().unwrap();
+        assert!(matches!(*app_error, AppError::ApiNotFound { ref api, .. } if api == "get_gmaes"));
+    }
+}
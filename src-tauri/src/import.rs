@@ -0,0 +1,135 @@
+use crate::database::{ensure_platform, get_connection, insert_game, write_transaction};
+use crate::error::AppError;
+use crate::models::GameRelations;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize)]
+pub struct SkippedImport {
+    pub name: String,
+    pub reason: String,
+}
+
+#[derive(Serialize)]
+pub struct ImportSummary {
+    pub imported: usize,
+    pub skipped: Vec<SkippedImport>,
+}
+
+// Playnite exports reference platforms/companies either as plain names or as `{ "Name": ... }` objects
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PlayniteNamed {
+    Name(String),
+    Object {
+        #[serde(rename = "Name")]
+        name: String,
+    },
+}
+
+impl PlayniteNamed {
+    fn into_name(self) -> String {
+        match self {
+            PlayniteNamed::Name(name) | PlayniteNamed::Object { name } => name,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PlayniteGame {
+    name: Option<String>,
+    description: Option<String>,
+    platforms: Option<Vec<PlayniteNamed>>,
+    developers: Option<Vec<PlayniteNamed>>,
+    publishers: Option<Vec<PlayniteNamed>>,
+    release_date: Option<Value>,
+    install_directory: Option<String>,
+    /// Total playtime in seconds
+    playtime: Option<u64>,
+    last_activity: Option<String>,
+    favorite: Option<bool>,
+}
+
+fn first_name(names: Option<Vec<PlayniteNamed>>) -> Option<String> {
+    names.and_then(|names| names.into_iter().next()).map(PlayniteNamed::into_name).filter(|name| !name.trim().is_empty())
+}
+
+// Release dates are exported either as a string or as `{ "ReleaseDate": "..." }`
+fn release_date(value: Option<Value>) -> Option<String> {
+    match value? {
+        Value::String(date) => Some(date),
+        Value::Object(map) => map.get("ReleaseDate").and_then(Value::as_str).map(str::to_string),
+        _ => None,
+    }
+}
+
+fn import_playnite_game(conn: &Connection, game: PlayniteGame, name: String) -> Result<(), String> {
+    let platform = first_name(game.platforms).ok_or_else(|| "no platform".to_string())?;
+    // The game and the stats carried over from Playnite land together or not at all
+    let tx = write_transaction(conn).map_err(|e| e.to_string())?;
+    let platform_id = ensure_platform(&tx, &platform, None).map_err(|e| e.to_string())?;
+    let game_id = insert_game(
+        &tx,
+        name,
+        platform_id,
+        game.description,
+        first_name(game.developers),
+        first_name(game.publishers),
+        release_date(game.release_date),
+        None,
+        None,
+        game.install_directory,
+        None,
+        false,
+        &GameRelations::default(),
+    )
+    .map_err(|e| match e {
+        AppError::DuplicateGame { .. } => "already in library".to_string(),
+        other => other.to_string(),
+    })?;
+    tx.execute(
+        "UPDATE games SET playtime_minutes = ?, last_played = ?, is_favorite = ?, updated_at = ? WHERE id = ?",
+        rusqlite::params![
+            (game.playtime.unwrap_or(0) / 60) as i64,
+            game.last_activity,
+            game.favorite.unwrap_or(false),
+            chrono::Utc::now().to_rfc3339(),
+            game_id
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())
+}
+
+/// Imports a Playnite library export (a JSON array of games). Platforms are created as needed;
+/// entries that can't be mapped are reported in `skipped` instead of aborting the import.
+#[tauri::command]
+pub fn import_from_playnite(app: tauri::AppHandle, export_path: String) -> Result<ImportSummary, String> {
+    let content = std::fs::read_to_string(&export_path).map_err(|e| format!("Failed to read {}: {}", export_path, e))?;
+    let entries: Vec<Value> = serde_json::from_str(&content).map_err(|e| format!("Failed to parse Playnite export: {}", e))?;
+    let conn = get_connection(&app)?;
+
+    let mut summary = ImportSummary { imported: 0, skipped: Vec::new() };
+    for entry in entries {
+        let fallback_name = entry.get("Name").and_then(Value::as_str).unwrap_or("<unnamed>").to_string();
+        let game: PlayniteGame = match serde_json::from_value(entry) {
+            Ok(game) => game,
+            Err(e) => {
+                summary.skipped.push(SkippedImport { name: fallback_name, reason: format!("unreadable entry: {}", e) });
+                continue;
+            }
+        };
+        let Some(name) = game.name.clone().filter(|name| !name.trim().is_empty()) else {
+            summary.skipped.push(SkippedImport { name: fallback_name, reason: "missing name".to_string() });
+            continue;
+        };
+        match import_playnite_game(&conn, game, name.clone()) {
+            Ok(()) => summary.imported += 1,
+            Err(reason) => summary.skipped.push(SkippedImport { name, reason }),
+        }
+    }
+    println!("Playnite import: {} imported, {} skipped", summary.imported, summary.skipped.len());
+    Ok(summary)
+}
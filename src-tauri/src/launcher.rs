@@ -0,0 +1,251 @@
+use crate::database::{find_game_executable, get_connection, get_game, get_game_env_vars, get_platform};
+use crate::error::AppError;
+use crate::paths::resolve_path;
+use crate::sessions::{finish_game_session, SessionTracker};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::process::{Child, Command};
+
+#[derive(Debug, Serialize)]
+pub struct LaunchPlan {
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: String,
+    pub env: HashMap<String, String>,
+}
+
+/// Splits an argument string on whitespace, keeping single- or double-quoted runs together.
+pub fn split_arguments(arguments: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    for c in arguments.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            None if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+    args
+}
+
+/// Processes spawned by `launch_game_command`, per game. Each child is waited on in the
+/// background and removed when it exits, so what's left is what's actually still running.
+#[derive(Default)]
+pub struct RunningGames {
+    pids: Mutex<HashMap<i64, Vec<u32>>>,
+}
+
+impl RunningGames {
+    /// Spawns `command` for the game unless it already has a live process and `allow_multiple`
+    /// is off, in which case the running pid is reported instead.
+    fn spawn(&self, game_id: i64, command: &mut Command, allow_multiple: bool) -> Result<(u32, Child), AppError> {
+        // Held across the spawn so two rapid launches can't both pass the check
+        let mut pids = self.pids.lock().unwrap();
+        if let Some(pid) = pids.get(&game_id).and_then(|running| running.first()) {
+            if !allow_multiple {
+                return Err(AppError::AlreadyRunning { pid: *pid });
+            }
+        }
+        let child = command.spawn()?;
+        let pid = child.id().ok_or_else(|| AppError::Io(std::io::Error::new(std::io::ErrorKind::Other, "Process exited before it could be tracked")))?;
+        pids.entry(game_id).or_default().push(pid);
+        Ok((pid, child))
+    }
+
+    fn exited(&self, game_id: i64, pid: u32) {
+        let mut pids = self.pids.lock().unwrap();
+        if let Some(running) = pids.get_mut(&game_id) {
+            running.retain(|running_pid| *running_pid != pid);
+            if running.is_empty() {
+                pids.remove(&game_id);
+            }
+        }
+    }
+
+    /// Live process ids per game.
+    pub fn running(&self) -> HashMap<i64, Vec<u32>> {
+        self.pids.lock().unwrap().clone()
+    }
+}
+
+pub const GAME_EXITED_EVENT: &str = "game://exited";
+
+// A process that exits sooner than this is assumed to be a launcher that handed off to the real
+// game (Steam, Epic, many .bat wrappers), so its session is left open to be ended by hand
+const MIN_TRACKED_RUNTIME: Duration = Duration::from_secs(30);
+
+#[derive(Serialize, Clone)]
+struct GameExited {
+    game_id: i64,
+    pid: u32,
+    exit_code: Option<i32>,
+    /// The game's new total playtime when its session was ended automatically
+    playtime_minutes: Option<i64>,
+}
+
+// Waits for the child in the background; on exit, ends the play session and emits `game://exited`.
+// Limitation: only the spawned process is watched. A launcher that starts the real game and
+// exits leaves the session open (see MIN_TRACKED_RUNTIME), and its end must be recorded manually.
+fn watch_game_exit(app: AppHandle, game_id: i64, pid: u32, mut child: Child) {
+    let started = Instant::now();
+    tauri::async_runtime::spawn(async move {
+        let status = child.wait().await;
+        app.state::<RunningGames>().exited(game_id, pid);
+        let playtime_minutes = if started.elapsed() >= MIN_TRACKED_RUNTIME {
+            match finish_game_session(&app, &app.state::<SessionTracker>(), game_id) {
+                Ok(total) => Some(total),
+                Err(e) => {
+                    println!("Could not end session for game {} after exit: {}", game_id, e);
+                    None
+                }
+            }
+        } else {
+            println!("Game {} (pid {}) exited after {:?}; leaving its session open", game_id, pid, started.elapsed());
+            None
+        };
+        let payload = GameExited { game_id, pid, exit_code: status.ok().and_then(|status| status.code()), playtime_minutes };
+        if let Err(e) = app.emit(GAME_EXITED_EVENT, payload) {
+            println!("Failed to emit {} for game {}: {}", GAME_EXITED_EVENT, game_id, e);
+        }
+    });
+}
+
+const MAX_LAUNCH_ERRORS: usize = 100;
+
+#[derive(Serialize, Clone)]
+pub struct LaunchError {
+    pub game_id: i64,
+    pub timestamp: String,
+    pub message: String,
+}
+
+/// Recent launch failures, oldest dropped first once `MAX_LAUNCH_ERRORS` is reached.
+#[derive(Default)]
+pub struct LaunchErrors {
+    entries: Mutex<VecDeque<LaunchError>>,
+}
+
+impl LaunchErrors {
+    /// Stores the failure and hands the message back so it can still be returned to the caller.
+    fn record(&self, game_id: i64, message: String) -> String {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() == MAX_LAUNCH_ERRORS {
+            entries.pop_front();
+        }
+        entries.push_back(LaunchError { game_id, timestamp: chrono::Utc::now().to_rfc3339(), message: message.clone() });
+        message
+    }
+}
+
+/// Works out exactly what launching a game would run, without spawning anything.
+/// `executable_label` picks one of the game's executables; otherwise its default is used,
+/// falling back to the game's own `executable_path`.
+pub fn build_launch_plan(conn: &Connection, game_id: i64, executable_label: Option<&str>) -> Result<LaunchPlan, AppError> {
+    let game = get_game(conn, game_id)?;
+    if !game.installed {
+        return Err(AppError::Validation(format!("{} is not installed", game.name)));
+    }
+    let platform = get_platform(conn, game.platform_id)?;
+
+    let (path, arguments) = match find_game_executable(conn, game_id, executable_label)? {
+        Some(executable) => (executable.path, executable.arguments),
+        None => match executable_label {
+            Some(label) => return Err(AppError::NotFound(format!("Game {} has no executable labelled {}", game_id, label))),
+            None => {
+                let path = game.executable_path.clone().ok_or_else(|| AppError::NotFound(format!("Game {} has no executable", game_id)))?;
+                (path, game.arguments.clone())
+            }
+        },
+    };
+
+    let base_directory = platform.base_directory.as_deref();
+    let program = resolve_path(base_directory, Some(path.as_str())).unwrap_or(path);
+    let cwd = match resolve_path(base_directory, game.effective_working_directory.as_deref()) {
+        Some(cwd) => cwd,
+        None => Path::new(&program)
+            .parent()
+            .map(|parent| parent.to_string_lossy().into_owned())
+            .filter(|parent| !parent.is_empty())
+            .unwrap_or_else(|| ".".to_string()),
+    };
+    let args = arguments.as_deref().map(split_arguments).unwrap_or_default();
+    let env = get_game_env_vars(conn, game_id)?;
+
+    Ok(LaunchPlan { program, args, cwd, env })
+}
+
+/// Launches a game and starts a play session for it. Returns the spawned process id.
+/// Failures are also kept in `LaunchErrors` for the troubleshooting panel.
+#[tauri::command]
+pub async fn launch_game_command(
+    app: AppHandle,
+    game_id: i64,
+    executable_label: Option<String>,
+    allow_multiple: Option<bool>,
+    tracker: State<'_, SessionTracker>,
+    running_games: State<'_, RunningGames>,
+    launch_errors: State<'_, LaunchErrors>,
+) -> Result<u32, String> {
+    let conn = get_connection(&app)?;
+    let plan = build_launch_plan(&conn, game_id, executable_label.as_deref()).map_err(|e| launch_errors.record(game_id, e.to_string()))?;
+    let mut command = Command::new(&plan.program);
+    command.args(&plan.args).current_dir(&plan.cwd).envs(&plan.env);
+    let (pid, child) = running_games.spawn(game_id, &mut command, allow_multiple.unwrap_or(false)).map_err(|e| match e {
+        // Refusing a second instance is expected behaviour, not something to troubleshoot
+        AppError::AlreadyRunning { .. } => e.to_string(),
+        AppError::Io(e) => launch_errors.record(game_id, format!("Failed to launch {}: {}", plan.program, e)),
+        other => launch_errors.record(game_id, other.to_string()),
+    })?;
+    tracker.start(game_id);
+    watch_game_exit(app, game_id, pid, child);
+    Ok(pid)
+}
+
+/// Process ids of launched games that are still running, keyed by game id.
+#[tauri::command]
+pub fn get_running_games(running_games: State<'_, RunningGames>) -> HashMap<i64, Vec<u32>> {
+    running_games.running()
+}
+
+/// Most recent launch failures first, up to `limit` (default all that are kept).
+#[tauri::command]
+pub fn get_recent_launch_errors(limit: Option<usize>, launch_errors: State<'_, LaunchErrors>) -> Vec<LaunchError> {
+    let entries = launch_errors.entries.lock().unwrap();
+    entries.iter().rev().take(limit.unwrap_or(MAX_LAUNCH_ERRORS)).cloned().collect()
+}
+
+#[tauri::command]
+pub fn clear_launch_errors(launch_errors: State<'_, LaunchErrors>) {
+    launch_errors.entries.lock().unwrap().clear();
+}
+
+/// Dry run of `launch_game_command`: returns the resolved program, arguments and working
+/// directory without spawning anything, failing in the same cases a real launch would.
+#[tauri::command]
+pub fn get_launch_command(app: AppHandle, game_id: i64, executable_label: Option<String>) -> Result<LaunchPlan, String> {
+    let conn = get_connection(&app)?;
+    build_launch_plan(&conn, game_id, executable_label.as_deref()).map_err(|e| e.to_string())
+}
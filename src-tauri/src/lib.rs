@@ -1,47 +1,155 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 mod database;
+mod encryption;
+mod error;
 mod models;
 mod extensions;
+mod import;
+mod launcher;
+mod library_csv;
+mod matching;
+mod paths;
+mod sessions;
+mod themes;
 
-use crate::database::{create_platform, get_platforms, update_platform, delete_platform, create_game, get_games, get_games_by_platform, update_game, delete_game};
+use crate::database::{get_connection, get_setting_value, set_setting_value, first_run_pending, seed_first_run_defaults, get_extension_setting_value, set_extension_setting_value, create_platform, get_platform, get_platforms, update_platform, delete_platform, create_game, get_game, get_games, get_games_by_platform, get_games_by_platforms, get_games_by_genre_name, query_games, set_game_installed, set_games_favorite, update_game, delete_game, add_playtime, add_game_executable, get_game_executables, delete_game_executable, get_library_stats, get_distinct_developers, get_distinct_publishers, search_games, search_platforms, set_game_metadata, get_game_metadata, delete_game_metadata, get_playtime_report, get_unplayed_games, get_recently_added, merge_platforms, set_game_source, get_game_source, set_game_cover, add_genre_to_games, get_genres_with_counts, delete_unused_genres, merge_genres, clone_game, sync_default_executable, compact_database, backup_database, clear_all_tables, find_orphaned_associations, repair_orphaned_associations, set_game_env_var, get_game_env_vars, delete_game_env_var, set_game_image, get_game_images, delete_game_image};
+use crate::encryption::encrypt_database;
+use crate::import::import_from_playnite;
+use crate::launcher::{LaunchErrors, RunningGames, clear_launch_errors, get_launch_command, get_recent_launch_errors, get_running_games, launch_game_command};
+use crate::library_csv::{export_platform_games_csv, import_games_csv};
+use crate::matching::match_game_name_command;
+use crate::paths::{cover_data_url, find_broken_paths, game_folder, import_cover_file, resolve_path, validate_executable_path};
+use crate::sessions::{SessionTracker, get_active_sessions, start_game_session, pause_game_session, resume_game_session, end_game_session};
+use crate::themes::{ThemeWatchers, set_active_theme, get_active_theme, get_theme_assets, watch_theme, stop_watching_theme};
 use arcadia_extension_framework::store::models::StoreSource;
 
-use rusqlite::Connection;
+use rusqlite::OptionalExtension;
 use tauri::{AppHandle, Manager, State};
+use tauri_plugin_opener::OpenerExt;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use arcadia_extension_framework::models::{ExtensionInfo, MenuItem};
+use arcadia_extension_framework::models::{ExtensionInfo, ExtensionType, MenuItem};
 use arcadia_extension_framework::store::manager::StoreManager;
-use crate::extensions::{ExtensionManager, fetch_store_extensions, fetch_extension_details, install_from_store, list_store_sources, add_store_source, remove_store_source, update_store_source};
+use crate::extensions::{ConfigField, ExtensionManager, ExtensionStatus, ExtensionSummary, HookResult, LibraryFilter, ManifestValidation, get_manifest_json_schema, InstallRegistry, ManifestCache, StoreSummaryCache, fetch_store_extensions, fetch_extension_details, fetch_extension_readme, install_from_store, cancel_install, list_store_sources, add_store_source, remove_store_source, update_store_source, set_store_source_enabled, reorder_store_sources, test_store_source, get_store_summary, get_all_available_updates};
 use serde_json::Value;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+// A missing key is `None`, not an error, so first-run defaults can be told apart from a broken database
 #[tauri::command]
-fn get_setting(app: AppHandle, key: String) -> Result<String, String> {
+fn get_setting(app: AppHandle, key: String) -> Result<Option<String>, String> {
     println!("get_setting called with key: {}", key);
-    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
-    let mut stmt = conn.prepare("SELECT value FROM settings WHERE key = ?").map_err(|e| e.to_string())?;
-    let value: String = stmt.query_row([key], |row| row.get(0)).map_err(|e| e.to_string())?;
-    println!("get_setting returning: {}", value);
+    let conn = get_connection(&app)?;
+    let value = get_setting_value(&conn, &key).map_err(|e| e.to_string())?;
+    println!("get_setting returning: {:?}", value);
     Ok(value)
 }
 
 #[tauri::command]
 fn set_setting(app: AppHandle, key: String, value: String) -> Result<(), String> {
     println!("set_setting called with key: {}, value: {}", key, value);
-    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
-    conn.execute("INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)", [key, value]).map_err(|e| e.to_string())?;
+    if key == extensions::STORE_MANIFEST_HOSTS_KEY {
+        extensions::parse_store_manifest_hosts(&value).map_err(|e| e.to_string())?;
+    }
+    let conn = get_connection(&app)?;
+    set_setting_value(&conn, &key, &value).map_err(|e| e.to_string())?;
+    if key == extensions::HTTP_PROXY_KEY || key == extensions::NO_PROXY_KEY {
+        extensions::load_store_proxy(&conn).map_err(|e| e.to_string())?;
+    }
+    if key == extensions::STORE_MANIFEST_HOSTS_KEY {
+        extensions::load_store_manifest_hosts(&conn).map_err(|e| e.to_string())?;
+    }
     Ok(())
 }
 
 #[tauri::command]
-fn get_app_data(app: AppHandle, data_type: String) -> Result<Vec<String>, String> {
+fn is_first_run(app: AppHandle) -> Result<bool, String> {
+    let conn = get_connection(&app)?;
+    first_run_pending(&conn).map_err(|e| e.to_string())
+}
+
+/// Seeds default settings and the "PC" platform and marks onboarding as done. Idempotent.
+#[tauri::command]
+fn complete_first_run(app: AppHandle) -> Result<(), String> {
+    let conn = get_connection(&app)?;
+    seed_first_run_defaults(&conn).map_err(|e| e.to_string())
+}
+
+/// Compacts the database (WAL checkpoint + VACUUM). Meant for an idle "compact database" action.
+#[tauri::command]
+fn maintain_database(app: AppHandle) -> Result<crate::models::DatabaseMaintenance, String> {
+    let db_path = database::get_db_path(&app).map_err(|e| e.to_string())?;
+    let conn = get_connection(&app)?;
+    compact_database(&conn, &db_path).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_app_paths(app: AppHandle, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<crate::models::AppPaths, String> {
+    let path_string = |path: &std::path::Path| path.to_string_lossy().into_owned();
     let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+    let extensions_dir = path_string(extension_manager.inner().read().await.extensions_dir());
+    Ok(crate::models::AppPaths {
+        data_dir: path_string(&data_dir),
+        db_path: path_string(&database::get_db_path(&app).map_err(|e| e.to_string())?),
+        extensions_dir,
+        covers_dir: path_string(&database::get_covers_dir(&app).map_err(|e| e.to_string())?),
+        backups_dir: path_string(&database::get_backups_dir(&app).map_err(|e| e.to_string())?),
+    })
+}
+
+// Must be passed verbatim to reset_all_data so the frontend can't wipe the library by accident
+const RESET_CONFIRMATION: &str = "DELETE EVERYTHING";
+
+/// Backs up the database to `backups/`, unloads every extension and deletes the library,
+/// extension and app data. Store sources and settings are left as they are.
+#[tauri::command]
+async fn reset_all_data(app: AppHandle, confirm: String, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<crate::models::ResetSummary, String> {
+    if confirm != RESET_CONFIRMATION {
+        return Err(crate::error::AppError::Validation(format!("Type \"{}\" to confirm the reset", RESET_CONFIRMATION)).to_string());
+    }
+    let db_path = database::get_db_path(&app).map_err(|e| e.to_string())?;
+    let backups_dir = database::get_backups_dir(&app).map_err(|e| e.to_string())?;
+    let backup_path = {
+        let conn = get_connection(&app)?;
+        backup_database(&conn, &db_path, &backups_dir).map_err(|e| e.to_string())?
+    };
+
+    let mut extensions_unloaded = 0;
+    {
+        let mut manager = extension_manager.inner().write().await;
+        for extension in manager.list_extensions() {
+            match manager.unload_extension(&extension.id).await {
+                Ok(()) => extensions_unloaded += 1,
+                Err(e) => println!("Failed to unload extension {} during reset: {}", extension.id, e),
+            }
+        }
+    }
+
+    let conn = get_connection(&app)?;
+    let cleared = clear_all_tables(&conn).map_err(|e| e.to_string())?;
+    Ok(crate::models::ResetSummary {
+        backup_path: backup_path.to_string_lossy().into_owned(),
+        extensions_unloaded,
+        cleared,
+    })
+}
+
+#[tauri::command]
+fn find_orphaned_associations_command(app: AppHandle) -> Result<Vec<crate::models::OrphanedRow>, String> {
+    let conn = get_connection(&app)?;
+    find_orphaned_associations(&conn).map_err(|e| e.to_string())
+}
+
+/// Deletes association rows whose game, genre or extension is gone. Returns the count removed.
+#[tauri::command]
+fn repair_orphaned_associations_command(app: AppHandle) -> Result<usize, String> {
+    let conn = get_connection(&app)?;
+    repair_orphaned_associations(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_app_data(app: AppHandle, data_type: String) -> Result<Vec<String>, String> {
+    let conn = get_connection(&app)?;
     let mut stmt = conn.prepare("SELECT data FROM app_data WHERE data_type = ?").map_err(|e| e.to_string())?;
     let rows = stmt.query_map([data_type], |row| row.get::<_, String>(0)).map_err(|e| e.to_string())?;
     let mut result = Vec::new();
@@ -53,9 +161,7 @@ fn get_app_data(app: AppHandle, data_type: String) -> Result<Vec<String>, String
 
 #[tauri::command]
 fn save_app_data(app: AppHandle, data_type: String, data: String) -> Result<i64, String> {
-    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+    let conn = get_connection(&app)?;
     conn.execute("INSERT INTO app_data (data_type, data) VALUES (?, ?)", [data_type, data]).map_err(|e| e.to_string())?;
     let id = conn.last_insert_rowid();
     Ok(id)
@@ -63,9 +169,7 @@ fn save_app_data(app: AppHandle, data_type: String, data: String) -> Result<i64,
 
 #[tauri::command]
 fn update_app_data(app: AppHandle, id: i64, data: String) -> Result<(), String> {
-    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+    let conn = get_connection(&app)?;
     let affected = conn.execute("UPDATE app_data SET data = ? WHERE id = ?", [data, id.to_string()]).map_err(|e| e.to_string())?;
     if affected == 0 {
         return Err("No row updated".to_string());
@@ -75,9 +179,7 @@ fn update_app_data(app: AppHandle, id: i64, data: String) -> Result<(), String>
 
 #[tauri::command]
 fn delete_app_data(app: AppHandle, id: i64) -> Result<(), String> {
-    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+    let conn = get_connection(&app)?;
     let affected = conn.execute("DELETE FROM app_data WHERE id = ?", [id]).map_err(|e| e.to_string())?;
     if affected == 0 {
         return Err("No row deleted".to_string());
@@ -87,9 +189,7 @@ fn delete_app_data(app: AppHandle, id: i64) -> Result<(), String> {
 
 #[tauri::command]
 fn get_extension_setting(app: AppHandle, extension_id: String, key: String) -> Result<String, String> {
-    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+    let conn = get_connection(&app)?;
     let mut stmt = conn.prepare("SELECT value FROM extension_settings WHERE extension_id = ? AND key = ?").map_err(|e| e.to_string())?;
     let value: String = stmt.query_row([extension_id, key], |row| row.get(0)).map_err(|e| e.to_string())?;
     Ok(value)
@@ -98,18 +198,46 @@ fn get_extension_setting(app: AppHandle, extension_id: String, key: String) -> R
 #[tauri::command]
 fn set_extension_setting(app: AppHandle, extension_id: String, key: String, value: String) -> Result<(), String> {
     println!("set_extension_setting called with extension_id: {}, key: {}, value: {}", extension_id, key, value);
-    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
-    conn.execute("INSERT OR REPLACE INTO extension_settings (extension_id, key, value) VALUES (?, ?, ?)", [extension_id, key, value]).map_err(|e| e.to_string())?;
-    Ok(())
+    let conn = get_connection(&app)?;
+    set_extension_setting_value(&conn, &extension_id, &key, &value).map_err(|e| e.to_string())
+}
+
+// Typed reads for config screens: a missing or unparseable value yields `default` instead of an error
+#[tauri::command]
+fn get_extension_setting_bool(app: AppHandle, extension_id: String, key: String, default: bool) -> Result<bool, String> {
+    let conn = get_connection(&app)?;
+    let value = get_extension_setting_value(&conn, &extension_id, &key).map_err(|e| e.to_string())?;
+    Ok(match value.as_deref().map(|v| v.trim().to_ascii_lowercase()) {
+        Some(v) if v == "true" || v == "1" => true,
+        Some(v) if v == "false" || v == "0" => false,
+        _ => default,
+    })
+}
+
+#[tauri::command]
+fn get_extension_setting_i64(app: AppHandle, extension_id: String, key: String, default: i64) -> Result<i64, String> {
+    let conn = get_connection(&app)?;
+    let value = get_extension_setting_value(&conn, &extension_id, &key).map_err(|e| e.to_string())?;
+    Ok(value.and_then(|v| v.trim().parse().ok()).unwrap_or(default))
+}
+
+#[tauri::command]
+fn get_extension_setting_json(app: AppHandle, extension_id: String, key: String, default: Value) -> Result<Value, String> {
+    let conn = get_connection(&app)?;
+    let value = get_extension_setting_value(&conn, &extension_id, &key).map_err(|e| e.to_string())?;
+    Ok(value.and_then(|v| serde_json::from_str(&v).ok()).unwrap_or(default))
+}
+
+#[tauri::command]
+fn set_extension_setting_json(app: AppHandle, extension_id: String, key: String, value: Value) -> Result<(), String> {
+    let conn = get_connection(&app)?;
+    let serialized = serde_json::to_string(&value).map_err(|e| e.to_string())?;
+    set_extension_setting_value(&conn, &extension_id, &key, &serialized).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 fn list_extension_settings(app: AppHandle, extension_id: String) -> Result<Vec<(String, String)>, String> {
-    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+    let conn = get_connection(&app)?;
     let mut stmt = conn.prepare("SELECT key, value FROM extension_settings WHERE extension_id = ?").map_err(|e| e.to_string())?;
     let rows = stmt.query_map([extension_id], |row| {
         Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
@@ -121,12 +249,24 @@ fn list_extension_settings(app: AppHandle, extension_id: String) -> Result<Vec<(
     Ok(result)
 }
 
+/// Clears an extension's settings and re-applies the manifest's `default_settings`, returning the result.
+#[tauri::command]
+async fn reset_extension_settings(app: AppHandle, extension_id: String, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<Vec<(String, String)>, String> {
+    let defaults = extension_manager.inner().read().await.get_default_settings(&extension_id).map_err(|e| e.to_string())?;
+    let mut conn = get_connection(&app)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    tx.execute("DELETE FROM extension_settings WHERE extension_id = ?", [&extension_id]).map_err(|e| e.to_string())?;
+    for (key, value) in &defaults {
+        set_extension_setting_value(&tx, &extension_id, key, value).map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(defaults.into_iter().collect())
+}
+
 #[tauri::command]
 fn delete_extension_setting(app: AppHandle, extension_id: String, key: String) -> Result<(), String> {
     println!("delete_extension_setting called with extension_id: {}, key: {}", extension_id, key);
-    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+    let conn = get_connection(&app)?;
     let affected = conn.execute("DELETE FROM extension_settings WHERE extension_id = ? AND key = ?", [extension_id, key]).map_err(|e| e.to_string())?;
     println!("delete_extension_setting affected {} rows", affected);
     if affected == 0 {
@@ -135,6 +275,81 @@ fn delete_extension_setting(app: AppHandle, extension_id: String, key: String) -
     Ok(())
 }
 
+#[tauri::command]
+fn export_extension_settings(app: AppHandle, extension_id: String) -> Result<String, String> {
+    let conn = get_connection(&app)?;
+    let mut stmt = conn.prepare("SELECT key, value FROM extension_settings WHERE extension_id = ?").map_err(|e| e.to_string())?;
+    let rows = stmt.query_map([extension_id], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    }).map_err(|e| e.to_string())?;
+    // BTreeMap keeps the exported keys sorted so backups diff cleanly
+    let mut settings = BTreeMap::new();
+    for row in rows {
+        let (key, value) = row.map_err(|e| e.to_string())?;
+        settings.insert(key, value);
+    }
+    serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn import_extension_settings(app: AppHandle, extension_id: String, json: String) -> Result<usize, String> {
+    let settings: BTreeMap<String, String> = serde_json::from_str(&json).map_err(|e| format!("Invalid settings JSON: {}", e))?;
+    let mut conn = get_connection(&app)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    for (key, value) in &settings {
+        set_extension_setting_value(&tx, &extension_id, key, value).map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(settings.len())
+}
+
+// Extension storage commands: runtime data (caches, tokens) kept apart from user settings
+#[tauri::command]
+fn extension_storage_get(app: AppHandle, extension_id: String, key: String) -> Result<Option<String>, String> {
+    let conn = get_connection(&app)?;
+    conn.query_row(
+        "SELECT value FROM extension_storage WHERE extension_id = ? AND key = ?",
+        [extension_id, key],
+        |row| row.get(0),
+    ).optional().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn extension_storage_set(app: AppHandle, extension_id: String, key: String, value: String) -> Result<(), String> {
+    serde_json::from_str::<Value>(&value).map_err(|e| format!("Value is not valid JSON: {}", e))?;
+    let conn = get_connection(&app)?;
+    conn.execute(
+        "INSERT INTO extension_storage (extension_id, key, value, updated_at) VALUES (?, ?, ?, CURRENT_TIMESTAMP)
+         ON CONFLICT(extension_id, key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        [extension_id, key, value],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+fn extension_storage_delete(app: AppHandle, extension_id: String, key: String) -> Result<(), String> {
+    let conn = get_connection(&app)?;
+    let affected = conn.execute("DELETE FROM extension_storage WHERE extension_id = ? AND key = ?", [extension_id, key]).map_err(|e| e.to_string())?;
+    if affected == 0 {
+        return Err("No row deleted".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn extension_storage_list(app: AppHandle, extension_id: String) -> Result<Vec<(String, String)>, String> {
+    let conn = get_connection(&app)?;
+    let mut stmt = conn.prepare("SELECT key, value FROM extension_storage WHERE extension_id = ? ORDER BY key").map_err(|e| e.to_string())?;
+    let rows = stmt.query_map([extension_id], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    }).map_err(|e| e.to_string())?;
+    let mut result = Vec::new();
+    for row in rows {
+        result.push(row.map_err(|e| e.to_string())?);
+    }
+    Ok(result)
+}
+
 #[tauri::command]
 async fn install_extension(_app: AppHandle, manifest_path: String, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<String, String> {
     let mut manager = extension_manager.inner().write().await;
@@ -142,10 +357,11 @@ async fn install_extension(_app: AppHandle, manifest_path: String, extension_man
     manager.load_extension(path).await.map_err(|e| e.to_string())
 }
 
+/// Uninstalls an extension and deletes its files unless `keep_files` is set. Returns the bytes freed.
 #[tauri::command]
-async fn uninstall_extension(_app: AppHandle, extension_id: String, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<(), String> {
+async fn uninstall_extension(_app: AppHandle, extension_id: String, keep_files: Option<bool>, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<u64, String> {
     let mut manager = extension_manager.inner().write().await;
-    manager.unload_extension(&extension_id).await.map_err(|e| e.to_string())
+    manager.uninstall_extension(&extension_id, keep_files.unwrap_or(false)).await.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -166,14 +382,142 @@ async fn list_extensions(extension_manager: State<'_, Arc<RwLock<ExtensionManage
     Ok(manager.list_extensions())
 }
 
+#[tauri::command]
+async fn list_extensions_by_type(extension_type: ExtensionType, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<Vec<ExtensionInfo>, String> {
+    let manager = extension_manager.inner().read().await;
+    Ok(manager.list_extensions_by_type(&extension_type))
+}
+
 #[tauri::command]
 async fn call_extension_api(_app: AppHandle, extension_id: String, api: String, params: Value, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<Value, String> {
     let manager = extension_manager.inner().read().await;
-    if let Some(extension) = manager.get_extension(&extension_id) {
-        extension.handle_hook(&api, params).await.map_err(|e| e.to_string())
-    } else {
-        Err("Extension not found".to_string())
-    }
+    manager.call_extension_api(&extension_id, &api, params).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_extension_hooks(extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<BTreeMap<String, Vec<String>>, String> {
+    let manager = extension_manager.inner().read().await;
+    Ok(manager.get_extension_hooks())
+}
+
+/// Sets the order an extension's hooks and menu items run in; lower runs first.
+#[tauri::command]
+async fn set_extension_priority(extension_id: String, priority: i32, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<(), String> {
+    let mut manager = extension_manager.inner().write().await;
+    manager.set_extension_priority(&extension_id, priority).await.map_err(|e| e.to_string())
+}
+
+/// Checks a manifest file the way installing it would, without installing. Unknown hook names
+/// are reported as warnings rather than errors.
+#[tauri::command]
+async fn validate_extension_manifest(manifest_path: String, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<ManifestValidation, String> {
+    let manager = extension_manager.inner().read().await;
+    Ok(manager.validate_manifest_file(std::path::Path::new(&manifest_path)))
+}
+
+#[tauri::command]
+async fn get_extension_manifest(extension_id: String, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<Value, String> {
+    let manager = extension_manager.inner().read().await;
+    manager.get_extension_manifest(&extension_id).map_err(|e| e.to_string())
+}
+
+/// Settings fields declared in the extension's `config_schema`, for rendering its settings form.
+#[tauri::command]
+async fn get_extension_config_schema(extension_id: String, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<Vec<ConfigField>, String> {
+    let manager = extension_manager.inner().read().await;
+    manager.get_config_schema(&extension_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_extension_status(extension_id: String, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<ExtensionStatus, String> {
+    let manager = extension_manager.inner().read().await;
+    Ok(manager.get_extension_status(&extension_id))
+}
+
+#[tauri::command]
+async fn get_extension_summary(extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<ExtensionSummary, String> {
+    let manager = extension_manager.inner().read().await;
+    Ok(manager.get_extension_summary())
+}
+
+#[tauri::command]
+async fn list_extension_apis(extension_id: String, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<Vec<String>, String> {
+    let manager = extension_manager.inner().read().await;
+    manager.list_extension_apis(&extension_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn call_hook_detailed(hook: String, params: Value, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<Vec<HookResult>, String> {
+    let manager = extension_manager.inner().read().await;
+    Ok(manager.call_hook_detailed(&hook, params).await)
+}
+
+#[tauri::command]
+async fn call_hook_first(hook: String, params: Value, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<Option<Value>, String> {
+    let manager = extension_manager.inner().read().await;
+    Ok(manager.call_hook_first(&hook, params).await)
+}
+
+#[tauri::command]
+async fn call_hook_merged(hook: String, params: Value, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<serde_json::Map<String, Value>, String> {
+    let manager = extension_manager.inner().read().await;
+    Ok(manager.call_hook_merged(&hook, params).await)
+}
+
+#[tauri::command]
+async fn get_library_filters(extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<Vec<LibraryFilter>, String> {
+    let manager = extension_manager.inner().read().await;
+    Ok(manager.get_library_filters().await)
+}
+
+/// Ids of the games matching an extension's library filter. Without `game_ids` the whole library is checked.
+#[tauri::command]
+async fn apply_library_filter(
+    app: AppHandle,
+    extension_id: String,
+    filter_id: String,
+    game_ids: Option<Vec<i64>>,
+    extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>,
+) -> Result<Vec<i64>, String> {
+    let game_ids = match game_ids {
+        Some(game_ids) => game_ids,
+        None => {
+            let conn = get_connection(&app)?;
+            get_games(&conn).map_err(|e| e.to_string())?.into_iter().map(|game| game.id).collect()
+        }
+    };
+    let manager = extension_manager.inner().read().await;
+    manager.apply_library_filter(&extension_id, &filter_id, game_ids).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn run_incremental_scan(extension_id: String, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<Value, String> {
+    let manager = extension_manager.inner().read().await;
+    manager.run_incremental_scan(&extension_id).await.map_err(|e| e.to_string())
+}
+
+/// Stores the games a game-library extension reports, returning their ids.
+#[tauri::command]
+async fn sync_extension_games(extension_id: String, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<Vec<i64>, String> {
+    let manager = extension_manager.inner().read().await;
+    manager.sync_extension_games(&extension_id).await.map_err(|e| e.to_string())
+}
+
+/// Read-only game query on behalf of an extension; needs its manifest to declare `db:games:read`.
+#[tauri::command]
+async fn extension_query_games(
+    extension_id: String,
+    filter: Option<crate::models::GameFilter>,
+    extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>,
+) -> Result<Vec<crate::models::Game>, String> {
+    let host = extension_manager.inner().read().await.host(&extension_id).map_err(|e| e.to_string())?;
+    host.query_games(&filter.unwrap_or_default()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_extension_last_scanned(extension_id: String, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<Option<String>, String> {
+    let manager = extension_manager.inner().read().await;
+    manager.get_last_scanned(&extension_id).await.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -186,37 +530,36 @@ async fn get_extension_menu_items(extension_manager: State<'_, Arc<RwLock<Extens
 
 // Platform commands
 #[tauri::command]
-fn create_platform_command(app: AppHandle, name: String, description: Option<String>, icon_path: Option<String>) -> Result<i64, String> {
-    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
-    create_platform(&conn, name, description, icon_path).map_err(|e| e.to_string())
+fn create_platform_command(app: AppHandle, name: String, description: Option<String>, icon_path: Option<String>, base_directory: Option<String>, default_working_directory: Option<String>, default_cover_path: Option<String>) -> Result<i64, String> {
+    let conn = get_connection(&app)?;
+    create_platform(&conn, name, description, icon_path, base_directory, default_working_directory, default_cover_path).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-fn get_platforms_command(app: AppHandle) -> Result<Vec<crate::models::Platform>, String> {
-    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
-    get_platforms(&conn).map_err(|e| e.to_string())
+fn get_platforms_command(app: AppHandle, sort_by: Option<crate::models::PlatformSort>) -> Result<Vec<crate::models::Platform>, String> {
+    let conn = get_connection(&app)?;
+    get_platforms(&conn, sort_by.unwrap_or_default()).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-fn update_platform_command(app: AppHandle, id: i64, name: String, description: Option<String>, icon_path: Option<String>) -> Result<(), String> {
-    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
-    update_platform(&conn, id, name, description, icon_path).map_err(|e| e.to_string())
+fn update_platform_command(app: AppHandle, id: i64, name: String, description: Option<String>, icon_path: Option<String>, base_directory: Option<String>, default_working_directory: Option<String>, default_cover_path: Option<String>) -> Result<(), String> {
+    let conn = get_connection(&app)?;
+    update_platform(&conn, id, name, description, icon_path, base_directory, default_working_directory, default_cover_path).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 fn delete_platform_command(app: AppHandle, id: i64) -> Result<(), String> {
-    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+    let conn = get_connection(&app)?;
     delete_platform(&conn, id).map_err(|e| e.to_string())
 }
 
+/// Folds duplicate platforms into `keep_id`. Returns how many games were moved.
+#[tauri::command]
+fn merge_platforms_command(app: AppHandle, keep_id: i64, merge_ids: Vec<i64>) -> Result<usize, String> {
+    let conn = get_connection(&app)?;
+    merge_platforms(&conn, keep_id, &merge_ids).map_err(|e| e.to_string())
+}
+
 // Game commands
 #[tauri::command]
 fn create_game_command(
@@ -231,29 +574,83 @@ fn create_game_command(
     executable_path: Option<String>,
     working_directory: Option<String>,
     arguments: Option<String>,
+    allow_duplicate: Option<bool>,
+    genres: Option<Vec<String>>,
+    env_vars: Option<HashMap<String, String>>,
+    extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>,
 ) -> Result<i64, String> {
-    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
-    create_game(&conn, name, platform_id, description, developer, publisher, release_date, cover_image_path, executable_path, working_directory, arguments).map_err(|e| e.to_string())
+    let conn = get_connection(&app)?;
+    let relations = crate::models::GameRelations { genres, env_vars };
+    let id = create_game(&conn, name, platform_id, description, developer, publisher, release_date, cover_image_path, executable_path, working_directory, arguments, allow_duplicate.unwrap_or(false), &relations).map_err(|e| e.to_string())?;
+    notify_game_event(&extension_manager, "on_game_added", id, get_game(&conn, id).ok());
+    Ok(id)
 }
 
+/// Duplicates a game as a template for a new entry and returns the copy's id.
 #[tauri::command]
-fn get_games_command(app: AppHandle) -> Result<Vec<crate::models::Game>, String> {
-    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
-    get_games(&conn).map_err(|e| e.to_string())
+fn clone_game_command(app: AppHandle, id: i64) -> Result<i64, String> {
+    let conn = get_connection(&app)?;
+    clone_game(&conn, id).map_err(|e| e.to_string())
+}
+
+/// All games, optionally narrowed to installed (`Some(true)`) or not-installed (`Some(false)`) ones
+/// and to an inclusive playtime range in minutes.
+#[tauri::command]
+fn get_games_command(
+    app: AppHandle,
+    installed: Option<bool>,
+    min_playtime_minutes: Option<i64>,
+    max_playtime_minutes: Option<i64>,
+) -> Result<Vec<crate::models::Game>, String> {
+    let conn = get_connection(&app)?;
+    if installed.is_none() && min_playtime_minutes.is_none() && max_playtime_minutes.is_none() {
+        return get_games(&conn).map_err(|e| e.to_string());
+    }
+    if let (Some(min), Some(max)) = (min_playtime_minutes, max_playtime_minutes) {
+        if min > max {
+            return Err(format!("min_playtime_minutes ({}) is greater than max_playtime_minutes ({})", min, max));
+        }
+    }
+    let filter = crate::models::GameFilter { installed, min_playtime_minutes, max_playtime_minutes, ..Default::default() };
+    query_games(&conn, &filter).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn set_game_installed_command(app: AppHandle, id: i64, installed: bool) -> Result<(), String> {
+    let conn = get_connection(&app)?;
+    set_game_installed(&conn, id, installed).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 fn get_games_by_platform_command(app: AppHandle, platform_id: i64) -> Result<Vec<crate::models::Game>, String> {
-    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+    let conn = get_connection(&app)?;
     get_games_by_platform(&conn, platform_id).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+fn get_games_by_platforms_command(app: AppHandle, platform_ids: Vec<i64>) -> Result<Vec<crate::models::Game>, String> {
+    let conn = get_connection(&app)?;
+    get_games_by_platforms(&conn, &platform_ids).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_games_by_genre_name_command(app: AppHandle, name: String) -> Result<Vec<crate::models::Game>, String> {
+    let conn = get_connection(&app)?;
+    get_games_by_genre_name(&conn, &name).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_unplayed_games_command(app: AppHandle, limit: u32, offset: u32) -> Result<Vec<crate::models::Game>, String> {
+    let conn = get_connection(&app)?;
+    get_unplayed_games(&conn, limit, offset).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_recently_added_command(app: AppHandle, days: u32, limit: u32) -> Result<Vec<crate::models::Game>, String> {
+    let conn = get_connection(&app)?;
+    get_recently_added(&conn, days, limit).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 fn update_game_command(
     app: AppHandle,
@@ -268,19 +665,333 @@ fn update_game_command(
     executable_path: Option<String>,
     working_directory: Option<String>,
     arguments: Option<String>,
+    genres: Option<Vec<String>>,
+    env_vars: Option<HashMap<String, String>>,
+    extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>,
 ) -> Result<(), String> {
-    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
-    update_game(&conn, id, name, platform_id, description, developer, publisher, release_date, cover_image_path, executable_path, working_directory, arguments).map_err(|e| e.to_string())
+    let conn = get_connection(&app)?;
+    let relations = crate::models::GameRelations { genres, env_vars };
+    update_game(&conn, id, name, platform_id, description, developer, publisher, release_date, cover_image_path, executable_path, working_directory, arguments, &relations).map_err(|e| e.to_string())?;
+    notify_game_event(&extension_manager, "on_game_updated", id, get_game(&conn, id).ok());
+    Ok(())
 }
 
 #[tauri::command]
-fn delete_game_command(app: AppHandle, id: i64) -> Result<(), String> {
-    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = data_dir.join("app.db");
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
-    delete_game(&conn, id).map_err(|e| e.to_string())
+fn delete_game_command(app: AppHandle, id: i64, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<(), String> {
+    let conn = get_connection(&app)?;
+    // Looked up first so subscribers still see what was deleted
+    let game = get_game(&conn, id).map_err(|e| e.to_string())?;
+    delete_game(&conn, id).map_err(|e| e.to_string())?;
+    notify_game_event(&extension_manager, "on_game_deleted", id, Some(game));
+    Ok(())
+}
+
+// Tells subscribed extensions about a library change without holding up the command.
+// For deletions `game` is the row as it was just before it was removed.
+fn notify_game_event(extension_manager: &Arc<RwLock<ExtensionManager>>, hook: &'static str, game_id: i64, game: Option<crate::models::Game>) {
+    let params = serde_json::json!({ "game_id": game_id, "game": game });
+    let extension_manager = extension_manager.clone();
+    tauri::async_runtime::spawn(async move {
+        for hook_result in extension_manager.read().await.notify_subscribers(hook, params).await {
+            if let Err(e) = hook_result.result {
+                println!("Extension {} failed handling {}: {}", hook_result.extension_id, hook, e);
+            }
+        }
+    });
+}
+
+#[tauri::command]
+fn set_game_source_command(app: AppHandle, game_id: i64, extension_id: String, external_id: String) -> Result<(), String> {
+    let conn = get_connection(&app)?;
+    set_game_source(&conn, game_id, &extension_id, &external_id).map_err(|e| e.to_string())
+}
+
+/// Re-pulls a game's metadata from the extension that added it via its `get_game_details` API,
+/// overwriting only the fields the extension returns.
+#[tauri::command]
+async fn refresh_game_from_source(app: AppHandle, game_id: i64, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<crate::models::Game, String> {
+    let (extension_id, external_id) = {
+        let conn = get_connection(&app)?;
+        get_game_source(&conn, game_id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Game {} was not added by an extension", game_id))?
+    };
+    let details = {
+        let manager = extension_manager.inner().read().await;
+        manager
+            .call_extension_api(&extension_id, "get_game_details", serde_json::json!({ "game_id": external_id }))
+            .await
+            .map_err(|e| e.to_string())?
+    };
+
+    let conn = get_connection(&app)?;
+    let game = get_game(&conn, game_id).map_err(|e| e.to_string())?;
+    let field = |key: &str, current: Option<String>| details.get(key).and_then(Value::as_str).map(str::to_string).or(current);
+    update_game(
+        &conn,
+        game_id,
+        field("name", Some(game.name.clone())).unwrap_or(game.name),
+        game.platform_id,
+        field("description", game.description),
+        field("developer", game.developer),
+        field("publisher", game.publisher),
+        field("release_date", game.release_date),
+        field("cover_image_path", game.cover_image_path),
+        game.executable_path,
+        game.working_directory,
+        game.arguments,
+        &crate::models::GameRelations::default(),
+    )
+    .map_err(|e| e.to_string())?;
+    get_game(&conn, game_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn set_games_favorite_command(app: AppHandle, ids: Vec<i64>, is_favorite: bool) -> Result<usize, String> {
+    let conn = get_connection(&app)?;
+    set_games_favorite(&conn, &ids, is_favorite).map_err(|e| e.to_string())
+}
+
+/// Adds externally tracked minutes to a game's playtime and returns the new total.
+#[tauri::command]
+fn add_playtime_command(app: AppHandle, game_id: i64, minutes: i64) -> Result<i64, String> {
+    let conn = get_connection(&app)?;
+    add_playtime(&conn, game_id, minutes).map_err(|e| e.to_string())
+}
+
+/// Playtime from recorded sessions that started within `[from, to)`, overall and per game/platform.
+#[tauri::command]
+fn get_playtime_report_command(app: AppHandle, from: String, to: String) -> Result<crate::models::PlaytimeReport, String> {
+    let conn = get_connection(&app)?;
+    get_playtime_report(&conn, &from, &to).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn open_game_folder(app: AppHandle, game_id: i64) -> Result<(), String> {
+    let conn = get_connection(&app)?;
+    let game = get_game(&conn, game_id).map_err(|e| e.to_string())?;
+    let folder = game_folder(&game).map_err(|e| e.to_string())?;
+    app.opener().open_path(folder.to_string_lossy(), None::<&str>).map_err(|e| e.to_string())
+}
+
+/// A game's local cover as a base64 `data:` URL, or `None` if it has none.
+#[tauri::command]
+fn get_game_cover(app: AppHandle, game_id: i64) -> Result<Option<String>, String> {
+    let conn = get_connection(&app)?;
+    let game = get_game(&conn, game_id).map_err(|e| e.to_string())?;
+    cover_data_url(&game).map_err(|e| e.to_string())
+}
+
+/// Copies an image into the app's covers folder and points the game's cover at the copy.
+#[tauri::command]
+fn set_game_cover_from_file(app: AppHandle, game_id: i64, src_path: String) -> Result<String, String> {
+    let conn = get_connection(&app)?;
+    get_game(&conn, game_id).map_err(|e| e.to_string())?;
+    let covers_dir = database::get_covers_dir(&app).map_err(|e| e.to_string())?;
+    let cover = import_cover_file(&covers_dir, game_id, std::path::Path::new(&src_path)).map_err(|e| e.to_string())?;
+    let cover = cover.to_string_lossy().into_owned();
+    set_game_cover(&conn, game_id, &cover).map_err(|e| e.to_string())?;
+    Ok(cover)
+}
+
+/// A warning to show if `executable_path` doesn't look launchable, or `None` if it does.
+/// Relative paths are resolved against `platform_id`'s base directory. Never blocks saving.
+#[tauri::command]
+fn check_executable_path(app: AppHandle, executable_path: String, platform_id: Option<i64>) -> Result<Option<String>, String> {
+    let base_directory = match platform_id {
+        Some(platform_id) => {
+            let conn = get_connection(&app)?;
+            get_platform(&conn, platform_id).map_err(|e| e.to_string())?.base_directory
+        }
+        None => None,
+    };
+    let path = resolve_path(base_directory.as_deref(), Some(&executable_path)).unwrap_or(executable_path);
+    Ok(validate_executable_path(std::path::Path::new(&path)).err().map(|e| e.to_string()))
+}
+
+#[tauri::command]
+fn find_broken_paths_command(app: AppHandle) -> Result<Vec<crate::models::BrokenPath>, String> {
+    let conn = get_connection(&app)?;
+    let games = get_games(&conn).map_err(|e| e.to_string())?;
+    Ok(find_broken_paths(&games))
+}
+
+/// Nulls every path reported by `find_broken_paths_command` and returns what was cleared.
+#[tauri::command]
+fn clear_broken_paths(app: AppHandle) -> Result<Vec<crate::models::BrokenPath>, String> {
+    let mut conn = get_connection(&app)?;
+    let games = get_games(&conn).map_err(|e| e.to_string())?;
+    let broken = find_broken_paths(&games);
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    for entry in &broken {
+        // `field` is always one of the fixed column names produced by find_broken_paths
+        tx.execute(&format!("UPDATE games SET {} = NULL WHERE id = ?", entry.field), [entry.game_id]).map_err(|e| e.to_string())?;
+        sync_default_executable(&tx, entry.game_id).map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(broken)
+}
+
+// Game executable commands
+#[tauri::command]
+fn add_game_executable_command(app: AppHandle, game_id: i64, label: String, path: String, arguments: Option<String>, is_default: bool) -> Result<i64, String> {
+    if label.trim().is_empty() || path.trim().is_empty() {
+        return Err("Executable label and path cannot be empty".to_string());
+    }
+    let conn = get_connection(&app)?;
+    add_game_executable(&conn, game_id, label, path, arguments, is_default).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_game_executables_command(app: AppHandle, game_id: i64) -> Result<Vec<crate::models::GameExecutable>, String> {
+    let conn = get_connection(&app)?;
+    get_game_executables(&conn, game_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn delete_game_executable_command(app: AppHandle, id: i64) -> Result<(), String> {
+    let conn = get_connection(&app)?;
+    let affected = delete_game_executable(&conn, id).map_err(|e| e.to_string())?;
+    if affected == 0 {
+        return Err("No row deleted".to_string());
+    }
+    Ok(())
+}
+
+// Game metadata commands
+/// Bulk-tags games with a genre. Returns the number of games newly tagged.
+#[tauri::command]
+fn add_genre_to_games_command(app: AppHandle, genre_id: i64, game_ids: Vec<i64>) -> Result<usize, String> {
+    let conn = get_connection(&app)?;
+    add_genre_to_games(&conn, genre_id, &game_ids).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_genres_with_counts_command(app: AppHandle) -> Result<Vec<crate::models::GenreWithCount>, String> {
+    let conn = get_connection(&app)?;
+    get_genres_with_counts(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn delete_unused_genres_command(app: AppHandle) -> Result<usize, String> {
+    let conn = get_connection(&app)?;
+    delete_unused_genres(&conn).map_err(|e| e.to_string())
+}
+
+/// Folds duplicate genres into `keep_id`. Returns how many game associations were moved.
+#[tauri::command]
+fn merge_genres_command(app: AppHandle, keep_id: i64, merge_ids: Vec<i64>) -> Result<usize, String> {
+    let conn = get_connection(&app)?;
+    merge_genres(&conn, keep_id, &merge_ids).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn set_game_metadata_command(app: AppHandle, game_id: i64, key: String, value: String) -> Result<(), String> {
+    if key.trim().is_empty() {
+        return Err("Metadata key cannot be empty".to_string());
+    }
+    let conn = get_connection(&app)?;
+    set_game_metadata(&conn, game_id, key, value).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_game_metadata_command(app: AppHandle, game_id: i64) -> Result<HashMap<String, String>, String> {
+    let conn = get_connection(&app)?;
+    get_game_metadata(&conn, game_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn delete_game_metadata_command(app: AppHandle, game_id: i64, key: String) -> Result<(), String> {
+    let conn = get_connection(&app)?;
+    let affected = delete_game_metadata(&conn, game_id, key).map_err(|e| e.to_string())?;
+    if affected == 0 {
+        return Err("No row deleted".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn set_game_env_var_command(app: AppHandle, game_id: i64, key: String, value: String) -> Result<(), String> {
+    let conn = get_connection(&app)?;
+    set_game_env_var(&conn, game_id, &key, &value).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn set_game_image_command(app: AppHandle, game_id: i64, image_type: String, path: String) -> Result<(), String> {
+    let conn = get_connection(&app)?;
+    set_game_image(&conn, game_id, &image_type, &path).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_game_images_command(app: AppHandle, game_id: i64) -> Result<BTreeMap<String, String>, String> {
+    let conn = get_connection(&app)?;
+    get_game_images(&conn, game_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn delete_game_image_command(app: AppHandle, game_id: i64, image_type: String) -> Result<(), String> {
+    let conn = get_connection(&app)?;
+    let affected = delete_game_image(&conn, game_id, &image_type).map_err(|e| e.to_string())?;
+    if affected == 0 {
+        return Err("No row deleted".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn get_game_env_vars_command(app: AppHandle, game_id: i64) -> Result<HashMap<String, String>, String> {
+    let conn = get_connection(&app)?;
+    get_game_env_vars(&conn, game_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn delete_game_env_var_command(app: AppHandle, game_id: i64, key: String) -> Result<(), String> {
+    let conn = get_connection(&app)?;
+    let affected = delete_game_env_var(&conn, game_id, &key).map_err(|e| e.to_string())?;
+    if affected == 0 {
+        return Err("No row deleted".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn get_distinct_developers_command(app: AppHandle) -> Result<Vec<String>, String> {
+    let conn = get_connection(&app)?;
+    get_distinct_developers(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_distinct_publishers_command(app: AppHandle) -> Result<Vec<String>, String> {
+    let conn = get_connection(&app)?;
+    get_distinct_publishers(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_library_stats_command(app: AppHandle) -> Result<crate::models::LibraryStats, String> {
+    let conn = get_connection(&app)?;
+    get_library_stats(&conn).map_err(|e| e.to_string())
+}
+
+/// Case-insensitive name search across games, platforms, and extensions, `limit` results each.
+#[tauri::command]
+async fn global_search(app: AppHandle, query: String, limit: u32, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<crate::models::GlobalSearchResults, String> {
+    let (games, platforms) = {
+        let conn = get_connection(&app)?;
+        let games = search_games(&conn, &query, limit).map_err(|e| e.to_string())?;
+        let platforms = search_platforms(&conn, &query, limit).map_err(|e| e.to_string())?;
+        (games, platforms)
+    };
+
+    let needle = query.to_lowercase();
+    let manager = extension_manager.inner().read().await;
+    let extensions = manager
+        .list_extensions()
+        .into_iter()
+        .filter(|ext| ext.name.to_lowercase().contains(&needle))
+        .take(limit as usize)
+        .collect();
+
+    Ok(crate::models::GlobalSearchResults { games, platforms, extensions })
 }
 
 #[tauri::command]
@@ -288,6 +999,9 @@ fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
+const EXTENSION_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+const APP_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     println!("Tauri app starting in debug mode");
@@ -296,13 +1010,29 @@ pub fn run() {
         .setup(|app| {
             println!("Setting up app");
             database::init_database(app).expect("Failed to init database");
+            extensions::load_store_proxy(&get_connection(app.handle())?)?;
+            if let Err(e) = extensions::load_store_manifest_hosts(&get_connection(app.handle())?) {
+                println!("Ignoring store manifest hosts: {}", e);
+            }
 
             // Initialize extension manager
-            let extension_dir = PathBuf::from("./extensions"); // Default extension directory
-            let extension_manager = ExtensionManager::new(app.handle().clone(), extension_dir.clone());
+            let extension_dir = database::resolve_extensions_dir(app)?;
+            println!("Using extension directory {}", extension_dir.display());
+            let mut extension_manager = ExtensionManager::new(app.handle().clone(), extension_dir);
+            match tauri::async_runtime::block_on(extension_manager.restore_extensions()) {
+                Ok(loaded) => println!("Restored {} extensions", loaded),
+                Err(e) => println!("Failed to restore extensions: {}", e),
+            }
 
 
             app.manage(Arc::new(RwLock::new(extension_manager)));
+            app.manage(InstallRegistry::default());
+            app.manage(ManifestCache::default());
+            app.manage(StoreSummaryCache::default());
+            app.manage(SessionTracker::default());
+            app.manage(RunningGames::default());
+            app.manage(LaunchErrors::default());
+            app.manage(ThemeWatchers::default());
 
             // Initialize store manager
             let mut store_manager = StoreManager::new();
@@ -331,7 +1061,21 @@ pub fn run() {
 
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![greet, get_setting, set_setting, get_app_data, save_app_data, update_app_data, delete_app_data, get_extension_setting, set_extension_setting, list_extension_settings, delete_extension_setting, install_extension, uninstall_extension, enable_extension, disable_extension, list_extensions, call_extension_api, get_extension_menu_items, fetch_store_extensions, fetch_extension_details, install_from_store, list_store_sources, add_store_source, remove_store_source, update_store_source, create_platform_command, get_platforms_command, update_platform_command, delete_platform_command, create_game_command, get_games_command, get_games_by_platform_command, update_game_command, delete_game_command])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .invoke_handler(tauri::generate_handler![greet, get_setting, set_setting, is_first_run, complete_first_run, get_app_data, save_app_data, update_app_data, delete_app_data, get_extension_setting, set_extension_setting, get_extension_setting_bool, get_extension_setting_i64, get_extension_setting_json, set_extension_setting_json, list_extension_settings, reset_extension_settings, delete_extension_setting, export_extension_settings, import_extension_settings, extension_storage_get, extension_storage_set, extension_storage_delete, extension_storage_list, install_extension, uninstall_extension, enable_extension, disable_extension, list_extensions, list_extensions_by_type, get_extension_status, get_extension_summary, get_extension_manifest, validate_extension_manifest, get_extension_config_schema, set_extension_priority, call_extension_api, list_extension_apis, get_extension_hooks, call_hook_detailed, call_hook_first, call_hook_merged, get_library_filters, apply_library_filter, run_incremental_scan, sync_extension_games, extension_query_games, get_extension_last_scanned, get_extension_menu_items, get_manifest_json_schema, set_active_theme, get_active_theme, get_theme_assets, watch_theme, stop_watching_theme, fetch_store_extensions, fetch_extension_details, fetch_extension_readme, install_from_store, cancel_install, list_store_sources, add_store_source, remove_store_source, update_store_source, set_store_source_enabled, reorder_store_sources, test_store_source, get_store_summary, get_all_available_updates, create_platform_command, get_platforms_command, update_platform_command, delete_platform_command, merge_platforms_command, create_game_command, clone_game_command, get_games_command, set_game_installed_command, get_games_by_platform_command, get_games_by_platforms_command, get_games_by_genre_name_command, get_unplayed_games_command, get_recently_added_command, update_game_command, delete_game_command, set_game_source_command, refresh_game_from_source, set_games_favorite_command, add_playtime_command, get_playtime_report_command, launch_game_command, get_running_games, get_recent_launch_errors, clear_launch_errors, get_launch_command, add_game_executable_command, get_game_executables_command, delete_game_executable_command, open_game_folder, get_game_cover, set_game_cover_from_file, check_executable_path, find_broken_paths_command, clear_broken_paths, start_game_session, get_active_sessions, pause_game_session, resume_game_session, end_game_session, add_genre_to_games_command, get_genres_with_counts_command, delete_unused_genres_command, merge_genres_command, set_game_metadata_command, get_game_metadata_command, delete_game_metadata_command, set_game_image_command, get_game_images_command, delete_game_image_command, set_game_env_var_command, get_game_env_vars_command, delete_game_env_var_command, get_distinct_developers_command, get_distinct_publishers_command, get_library_stats_command, global_search, match_game_name_command, import_from_playnite, export_platform_games_csv, import_games_csv, encrypt_database, maintain_database, get_app_paths, reset_all_data, find_orphaned_associations_command, repair_orphaned_associations_command])
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let tauri::RunEvent::Exit = event {
+                let extension_manager = app_handle.state::<Arc<RwLock<ExtensionManager>>>().inner().clone();
+                tauri::async_runtime::block_on(async move {
+                    // Bound the whole shutdown, including waiting for the manager lock
+                    let shutdown = async move {
+                        extension_manager.write().await.shutdown_all(EXTENSION_SHUTDOWN_TIMEOUT).await;
+                    };
+                    if tokio::time::timeout(APP_SHUTDOWN_TIMEOUT, shutdown).await.is_err() {
+                        println!("Timed out shutting down extensions, exiting anyway");
+                    }
+                });
+            }
+        });
 }
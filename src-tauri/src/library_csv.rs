@@ -0,0 +1,157 @@
+use crate::database::{get_connection, get_games_by_platform, get_platform, insert_game, write_transaction};
+use crate::error::AppError;
+use crate::import::{ImportSummary, SkippedImport};
+use crate::models::GameRelations;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+/// One spreadsheet row; the header row uses these field names.
+#[derive(Serialize, Deserialize)]
+struct CsvGame {
+    name: String,
+    #[serde(default)]
+    developer: Option<String>,
+    #[serde(default)]
+    publisher: Option<String>,
+    #[serde(default)]
+    release_date: Option<String>,
+    #[serde(default)]
+    playtime_minutes: Option<i64>,
+    #[serde(default)]
+    last_played: Option<String>,
+}
+
+const CSV_HEADER: [&str; 6] = ["name", "developer", "publisher", "release_date", "playtime_minutes", "last_played"];
+
+fn csv_error(e: csv::Error) -> AppError {
+    AppError::Validation(format!("Invalid CSV: {}", e))
+}
+
+pub fn export_platform_games(conn: &Connection, platform_id: i64) -> Result<String, AppError> {
+    get_platform(conn, platform_id)?;
+    // Headers are written by hand so a platform with no games still exports a header row
+    let mut writer = csv::WriterBuilder::new().has_headers(false).from_writer(Vec::new());
+    writer.write_record(CSV_HEADER).map_err(csv_error)?;
+    for game in get_games_by_platform(conn, platform_id)? {
+        writer
+            .serialize(CsvGame {
+                name: game.name,
+                developer: game.developer,
+                publisher: game.publisher,
+                release_date: game.release_date,
+                playtime_minutes: Some(game.playtime_minutes),
+                last_played: game.last_played,
+            })
+            .map_err(csv_error)?;
+    }
+    let bytes = writer.into_inner().map_err(|e| AppError::Io(e.into_error()))?;
+    String::from_utf8(bytes).map_err(|e| AppError::Validation(e.to_string()))
+}
+
+fn import_csv_game(conn: &Connection, platform_id: i64, row: CsvGame) -> Result<(), String> {
+    // A row whose playtime can't be written leaves no game behind
+    let tx = write_transaction(conn).map_err(|e| e.to_string())?;
+    let game_id = insert_game(&tx, row.name, platform_id, None, row.developer, row.publisher, row.release_date, None, None, None, None, false, &GameRelations::default())
+        .map_err(|e| match e {
+            AppError::DuplicateGame { .. } => "already in library".to_string(),
+            other => other.to_string(),
+        })?;
+    tx.execute(
+        "UPDATE games SET playtime_minutes = ?, last_played = ?, updated_at = ? WHERE id = ?",
+        rusqlite::params![row.playtime_minutes.unwrap_or(0), row.last_played, chrono::Utc::now().to_rfc3339(), game_id],
+    )
+    .map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())
+}
+
+pub fn import_platform_games(conn: &Connection, platform_id: i64, csv: &str) -> Result<ImportSummary, AppError> {
+    get_platform(conn, platform_id)?;
+    let mut reader = csv::Reader::from_reader(csv.as_bytes());
+    let mut summary = ImportSummary { imported: 0, skipped: Vec::new() };
+    for (index, record) in reader.deserialize::<CsvGame>().enumerate() {
+        // Row 1 is the header
+        let row_name = format!("<row {}>", index + 2);
+        let row = match record {
+            Ok(row) => row,
+            Err(e) => {
+                summary.skipped.push(SkippedImport { name: row_name, reason: format!("unreadable row: {}", e) });
+                continue;
+            }
+        };
+        if row.name.trim().is_empty() {
+            summary.skipped.push(SkippedImport { name: row_name, reason: "missing name".to_string() });
+            continue;
+        }
+        let name = row.name.clone();
+        match import_csv_game(conn, platform_id, row) {
+            Ok(()) => summary.imported += 1,
+            Err(reason) => summary.skipped.push(SkippedImport { name, reason }),
+        }
+    }
+    Ok(summary)
+}
+
+/// The platform's games as CSV: name, developer, publisher, release_date, playtime_minutes, last_played.
+#[tauri::command]
+pub fn export_platform_games_csv(app: tauri::AppHandle, platform_id: i64) -> Result<String, String> {
+    let conn = get_connection(&app)?;
+    export_platform_games(&conn, platform_id).map_err(|e| e.to_string())
+}
+
+/// Creates games on the platform from CSV in the export's format. Only `name` is required;
+/// rows that fail are reported in `skipped`.
+#[tauri::command]
+pub fn import_games_csv(app: tauri::AppHandle, platform_id: i64, csv: String) -> Result<ImportSummary, String> {
+    let conn = get_connection(&app)?;
+    import_platform_games(&conn, platform_id, &csv).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{create_game, create_platform, create_schema, open_connection};
+    use std::path::Path;
+
+    #[test]
+    fn export_then_import_round_trips_quoted_fields() {
+        let conn = open_connection(Path::new(":memory:")).unwrap();
+        create_schema(&conn).unwrap();
+        let source = create_platform(&conn, "PC".to_string(), None, None, None, None, None).unwrap();
+        let target = create_platform(&conn, "Backup".to_string(), None, None, None, None, None).unwrap();
+        let game_id = create_game(
+            &conn,
+            "Game, \"The\" Sequel\nDeluxe".to_string(),
+            source,
+            None,
+            Some("Studio, Inc.".to_string()),
+            Some("\"Quoted\" Publisher".to_string()),
+            Some("2020-01-02".to_string()),
+            None,
+            None,
+            None,
+            None,
+            false,
+            &GameRelations::default(),
+        )
+        .unwrap();
+        conn.execute(
+            "UPDATE games SET playtime_minutes = 95, last_played = '2024-03-04T05:06:07+00:00' WHERE id = ?",
+            [game_id],
+        )
+        .unwrap();
+
+        let csv = export_platform_games(&conn, source).unwrap();
+        let summary = import_platform_games(&conn, target, &csv).unwrap();
+
+        assert_eq!(summary.imported, 1);
+        assert!(summary.skipped.is_empty());
+        let original = &get_games_by_platform(&conn, source).unwrap()[0];
+        let imported = &get_games_by_platform(&conn, target).unwrap()[0];
+        assert_eq!(imported.name, original.name);
+        assert_eq!(imported.developer, original.developer);
+        assert_eq!(imported.publisher, original.publisher);
+        assert_eq!(imported.release_date, original.release_date);
+        assert_eq!(imported.playtime_minutes, 95);
+        assert_eq!(imported.last_played, original.last_played);
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::BTreeSet;
+
+// Lowercases, drops bracketed suffixes like "(Game of the Year Edition)" and reduces
+// punctuation to single spaces
+fn normalize(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut depth = 0usize;
+    for c in name.chars() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            _ if depth > 0 => {}
+            c if c.is_alphanumeric() => out.extend(c.to_lowercase()),
+            _ => out.push(' '),
+        }
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Similarity in 0.0..=1.0 from edit distance.
+fn ratio(a: &str, b: &str) -> f64 {
+    let len = a.chars().count().max(b.chars().count());
+    if len == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(a, b) as f64 / len as f64
+}
+
+// Token-set ratio: compares the shared words against each side's full word set,
+// so extra words on one side (editions, subtitles) cost little
+fn token_set_ratio(a: &str, b: &str) -> f64 {
+    let tokens_a: BTreeSet<&str> = a.split(' ').filter(|t| !t.is_empty()).collect();
+    let tokens_b: BTreeSet<&str> = b.split(' ').filter(|t| !t.is_empty()).collect();
+    let join = |tokens: Vec<&str>| tokens.join(" ");
+    let common = join(tokens_a.intersection(&tokens_b).copied().collect());
+    let with_rest = |rest: Vec<&str>| {
+        let rest = join(rest);
+        format!("{} {}", common, rest).trim().to_string()
+    };
+    let full_a = with_rest(tokens_a.difference(&tokens_b).copied().collect());
+    let full_b = with_rest(tokens_b.difference(&tokens_a).copied().collect());
+    if common.is_empty() {
+        return ratio(&full_a, &full_b);
+    }
+    ratio(&common, &full_a).max(ratio(&common, &full_b)).max(ratio(&full_a, &full_b))
+}
+
+/// Scores each candidate title against `query` (0.0 to 1.0) and returns those scoring at least
+/// `threshold`, best first. Callers can treat several close scores as ambiguous and ask the user.
+pub fn match_game_name(query: &str, candidates: &[String], threshold: f64) -> Vec<(String, f64)> {
+    let query = normalize(query);
+    let mut matches: Vec<(String, f64)> = candidates
+        .iter()
+        .map(|candidate| {
+            let normalized = normalize(candidate);
+            let score = ratio(&query, &normalized).max(token_set_ratio(&query, &normalized));
+            (candidate.clone(), score)
+        })
+        .filter(|(_, score)| *score >= threshold)
+        .collect();
+    matches.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    matches
+}
+
+#[tauri::command]
+pub fn match_game_name_command(query: String, candidates: Vec<String>, threshold: Option<f64>) -> Vec<(String, f64)> {
+    match_game_name(&query, &candidates, threshold.unwrap_or(0.9))
+}
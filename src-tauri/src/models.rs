@@ -1,4 +1,6 @@
+use arcadia_extension_framework::models::ExtensionInfo;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Platform {
@@ -6,10 +8,21 @@ pub struct Platform {
     pub name: String,
     pub description: Option<String>,
     pub icon_path: Option<String>,
+    pub base_directory: Option<String>,
+    pub default_working_directory: Option<String>,
+    pub default_cover_path: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub enum PlatformSort {
+    #[default]
+    Name,
+    CreatedAt,
+    GameCount,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Game {
     pub id: i64,
@@ -24,10 +37,140 @@ pub struct Game {
     pub working_directory: Option<String>,
     pub arguments: Option<String>,
     pub is_favorite: bool,
+    pub installed: bool,
+    /// Image types stored in game_images, e.g. ["banner", "cover"]
+    pub image_types: Vec<String>,
     pub playtime_minutes: i64,
     pub last_played: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    pub resolved_executable_path: Option<String>,
+    pub resolved_cover_image_path: Option<String>,
+    pub effective_working_directory: Option<String>,
+    pub display_cover_path: String,
+}
+
+/// Filter for read-only game queries. Unset fields don't restrict the results.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameFilter {
+    pub platform_id: Option<i64>,
+    pub search: Option<String>,
+    pub favorites_only: bool,
+    pub installed: Option<bool>,
+    /// Inclusive playtime bounds in minutes; `None` leaves that end open
+    pub min_playtime_minutes: Option<i64>,
+    pub max_playtime_minutes: Option<i64>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+/// Rows stored alongside a game that `create_game`/`update_game` write in the same transaction.
+/// `None` leaves that set untouched; `Some` replaces it.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameRelations {
+    pub genres: Option<Vec<String>>,
+    pub env_vars: Option<HashMap<String, String>>,
+}
+
+/// A game as reported by a GameLibrary extension, keyed by the extension's own `external_id`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SourceGame {
+    pub external_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub developer: Option<String>,
+    pub publisher: Option<String>,
+    pub release_date: Option<String>,
+    pub cover_image_path: Option<String>,
+    pub executable_path: Option<String>,
+    pub playtime_minutes: Option<i64>,
+    pub last_played: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GameExecutable {
+    pub id: i64,
+    pub game_id: i64,
+    pub label: String,
+    pub path: String,
+    pub arguments: Option<String>,
+    pub is_default: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LibraryStats {
+    pub total_games: i64,
+    pub total_platforms: i64,
+    pub total_playtime_minutes: i64,
+    pub favorite_count: i64,
+    pub most_played_game: Option<Game>,
+    pub games_by_platform: Vec<(String, i64)>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlaytimeEntry {
+    pub id: i64,
+    pub name: String,
+    pub minutes: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlaytimeReport {
+    pub from: String,
+    pub to: String,
+    pub total_minutes: i64,
+    pub by_game: Vec<PlaytimeEntry>,
+    pub by_platform: Vec<PlaytimeEntry>,
+}
+
+/// Database size in bytes (including the WAL) around a maintenance run.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DatabaseMaintenance {
+    pub size_before: u64,
+    pub size_after: u64,
+}
+
+/// Where the app keeps its files on this machine.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AppPaths {
+    pub data_dir: String,
+    pub db_path: String,
+    pub extensions_dir: String,
+    pub covers_dir: String,
+    pub backups_dir: String,
+}
+
+/// What `reset_all_data` did: where the pre-reset backup went and how many rows each table lost.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResetSummary {
+    pub backup_path: String,
+    pub extensions_unloaded: usize,
+    pub cleared: std::collections::BTreeMap<String, usize>,
+}
+
+/// An association row pointing at a game, genre or extension that no longer exists.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrphanedRow {
+    pub table: String,
+    pub rowid: i64,
+    pub references: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BrokenPath {
+    pub game_id: i64,
+    pub field: String,
+    pub path: String,
+}
+
+#[derive(Serialize)]
+pub struct GlobalSearchResults {
+    pub games: Vec<Game>,
+    pub platforms: Vec<Platform>,
+    pub extensions: Vec<ExtensionInfo>,
 }
 
 #[allow(dead_code)]
@@ -35,4 +178,11 @@ pub struct Game {
 pub struct Genre {
     pub id: i64,
     pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GenreWithCount {
+    pub id: i64,
+    pub name: String,
+    pub game_count: i64,
 }
\ No newline at end of file
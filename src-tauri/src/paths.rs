@@ -0,0 +1,183 @@
+use crate::error::AppError;
+use crate::models::{BrokenPath, Game};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Resolves a game path against its platform's base directory. Relative paths are joined onto
+/// `base_directory`; absolute paths, and any path when no base directory is set, pass through.
+pub fn resolve_path(base_directory: Option<&str>, path: Option<&str>) -> Option<String> {
+    let path = path?;
+    match base_directory {
+        Some(base) if Path::new(path).is_relative() => Some(Path::new(base).join(path).to_string_lossy().into_owned()),
+        _ => Some(path.to_string()),
+    }
+}
+
+/// The cover to display for a game: its own cover, else its platform's default cover, else a
+/// `placeholder:<letter>` reference keyed on the first letter of the name (`#` for non-letters).
+pub fn display_cover_path(cover_image_path: Option<&str>, platform_default_cover: Option<&str>, name: &str) -> String {
+    if let Some(cover) = cover_image_path.or(platform_default_cover) {
+        return cover.to_string();
+    }
+    let letter = name
+        .trim()
+        .chars()
+        .next()
+        .filter(|c| c.is_alphanumeric())
+        .map(|c| c.to_uppercase().to_string())
+        .unwrap_or_else(|| "#".to_string());
+    format!("placeholder:{}", letter)
+}
+
+/// The folder a game lives in: its executable's parent, falling back to its effective working directory.
+pub fn game_folder(game: &Game) -> Result<PathBuf, AppError> {
+    let folder = match (&game.resolved_executable_path, &game.effective_working_directory) {
+        (Some(executable), _) => Path::new(executable)
+            .parent()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| AppError::NotFound(format!("Executable path {} has no parent folder", executable)))?,
+        (None, Some(working_directory)) => PathBuf::from(working_directory),
+        (None, None) => return Err(AppError::NotFound(format!("Game {} has no executable path or working directory", game.id))),
+    };
+    if !folder.is_dir() {
+        return Err(AppError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("Folder {} does not exist", folder.display()),
+        )));
+    }
+    Ok(folder)
+}
+
+/// Checks that `path` looks launchable: it exists, is a file, and is executable (the execute
+/// bit on Unix, a launchable extension on Windows). Callers treat the error as a warning.
+pub fn validate_executable_path(path: &Path) -> Result<(), AppError> {
+    let metadata = std::fs::metadata(path).map_err(|_| AppError::Validation(format!("{} does not exist", path.display())))?;
+    if !metadata.is_file() {
+        return Err(AppError::Validation(format!("{} is not a file", path.display())));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if metadata.permissions().mode() & 0o111 == 0 {
+            return Err(AppError::Validation(format!("{} is not executable", path.display())));
+        }
+    }
+    #[cfg(windows)]
+    {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_ascii_lowercase();
+        if !["exe", "bat", "cmd", "lnk"].contains(&extension.as_str()) {
+            return Err(AppError::Validation(format!("{} is not an .exe, .bat, .cmd or .lnk file", path.display())));
+        }
+    }
+    Ok(())
+}
+
+fn is_remote(path: &str) -> bool {
+    path.starts_with("http://") || path.starts_with("https://")
+}
+
+// Covers bigger than this are rejected rather than pushed through IPC as base64
+const MAX_COVER_BYTES: u64 = 10 * 1024 * 1024;
+
+fn image_mime_type(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    Some(match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        "svg" => "image/svg+xml",
+        "ico" => "image/x-icon",
+        "avif" => "image/avif",
+        _ => return None,
+    })
+}
+
+/// Reads a game's local cover as a `data:` URL so the webview can show files outside the
+/// asset scope. `None` when the game has no local cover or the file is gone.
+pub fn cover_data_url(game: &Game) -> Result<Option<String>, AppError> {
+    use base64::Engine;
+
+    let Some(cover) = game.resolved_cover_image_path.as_deref().filter(|cover| !is_remote(cover)) else {
+        return Ok(None);
+    };
+    let path = Path::new(cover);
+    let metadata = match std::fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(AppError::Io(e)),
+    };
+    if metadata.len() > MAX_COVER_BYTES {
+        return Err(AppError::Validation(format!("Cover {} is larger than {} bytes", cover, MAX_COVER_BYTES)));
+    }
+    let mime_type = image_mime_type(path).ok_or_else(|| AppError::Validation(format!("Cover {} is not a supported image type", cover)))?;
+    let bytes = std::fs::read(path)?;
+    Ok(Some(format!("data:{};base64,{}", mime_type, base64::engine::general_purpose::STANDARD.encode(bytes))))
+}
+
+// Checks the file's leading bytes against the format its extension claims, so a renamed
+// non-image isn't stored as a cover. SVG is text, so it only has to mention an <svg element early on.
+fn has_image_signature(mime_type: &str, header: &[u8]) -> bool {
+    match mime_type {
+        "image/png" => header.starts_with(b"\x89PNG\r\n\x1a\n"),
+        "image/jpeg" => header.starts_with(&[0xff, 0xd8, 0xff]),
+        "image/gif" => header.starts_with(b"GIF87a") || header.starts_with(b"GIF89a"),
+        "image/webp" => header.len() >= 12 && &header[..4] == b"RIFF" && &header[8..12] == b"WEBP",
+        "image/bmp" => header.starts_with(b"BM"),
+        "image/x-icon" => header.starts_with(&[0, 0, 1, 0]),
+        "image/avif" => header.len() >= 12 && &header[4..8] == b"ftyp" && matches!(&header[8..12], b"avif" | b"avis"),
+        "image/svg+xml" => String::from_utf8_lossy(header).contains("<svg"),
+        _ => false,
+    }
+}
+
+/// Copies an image into `covers_dir` as `<game_id>.<ext>` so the cover no longer depends on
+/// the original file staying put. Returns the copied path.
+pub fn import_cover_file(covers_dir: &Path, game_id: i64, src_path: &Path) -> Result<PathBuf, AppError> {
+    let Some(mime_type) = image_mime_type(src_path) else {
+        return Err(AppError::Validation(format!("{} is not a supported image type", src_path.display())));
+    };
+    let metadata = std::fs::metadata(src_path)?;
+    if !metadata.is_file() {
+        return Err(AppError::Validation(format!("{} is not a file", src_path.display())));
+    }
+    if metadata.len() > MAX_COVER_BYTES {
+        return Err(AppError::Validation(format!("Cover {} is larger than {} bytes", src_path.display(), MAX_COVER_BYTES)));
+    }
+    let mut header = Vec::with_capacity(512);
+    std::fs::File::open(src_path)?.take(512).read_to_end(&mut header)?;
+    if !has_image_signature(mime_type, &header) {
+        return Err(AppError::Validation(format!("{} does not contain a valid {} image", src_path.display(), mime_type)));
+    }
+    let extension = src_path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_ascii_lowercase();
+    std::fs::create_dir_all(covers_dir)?;
+    let destination = covers_dir.join(format!("{}.{}", game_id, extension));
+    // Re-importing the stored cover would otherwise truncate it by copying it onto itself
+    if let (Ok(src), Ok(dest)) = (src_path.canonicalize(), destination.canonicalize()) {
+        if src == dest {
+            return Ok(destination);
+        }
+    }
+    std::fs::copy(src_path, &destination)?;
+    Ok(destination)
+}
+
+/// Local executable and cover paths that no longer exist on disk. Remote URLs are skipped.
+pub fn find_broken_paths(games: &[Game]) -> Vec<BrokenPath> {
+    let mut broken = Vec::new();
+    for game in games {
+        let fields = [
+            ("executable_path", &game.resolved_executable_path),
+            ("cover_image_path", &game.resolved_cover_image_path),
+        ];
+        for (field, path) in fields {
+            if let Some(path) = path {
+                if !is_remote(path) && !Path::new(path).exists() {
+                    broken.push(BrokenPath { game_id: game.id, field: field.to_string(), path: path.clone() });
+                }
+            }
+        }
+    }
+    broken
+}
@@ -0,0 +1,181 @@
+use crate::database::{get_connection, get_game, record_play_session};
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+struct Session {
+    started_at: DateTime<Utc>,
+    // Active time accrued before the current run; `running_since` is None while paused
+    accrued: Duration,
+    running_since: Option<Instant>,
+}
+
+impl Session {
+    fn active_time(&self) -> Duration {
+        self.accrued + self.running_since.map(|since| since.elapsed()).unwrap_or_default()
+    }
+}
+
+pub struct EndedSession {
+    pub started_at: DateTime<Utc>,
+    pub elapsed: Duration,
+}
+
+/// A session still being tracked, as shown by a "now playing" indicator.
+#[derive(Serialize)]
+pub struct ActiveSession {
+    pub game_id: i64,
+    pub started_at: String,
+    /// Unpaused time so far
+    pub elapsed_minutes: u64,
+    pub paused: bool,
+}
+
+/// In-memory play sessions. A game may be running more than once at a time, so each game keeps
+/// its open sessions oldest-first and ending a session always closes the oldest one.
+#[derive(Default)]
+pub struct SessionTracker {
+    sessions: Mutex<HashMap<i64, Vec<Session>>>,
+}
+
+impl SessionTracker {
+    pub fn start(&self, game_id: i64) {
+        let mut sessions = self.sessions.lock().unwrap();
+        sessions.entry(game_id).or_default().push(Session { started_at: Utc::now(), accrued: Duration::ZERO, running_since: Some(Instant::now()) });
+    }
+
+    pub fn end(&self, game_id: i64) -> Option<EndedSession> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let open = sessions.get_mut(&game_id)?;
+        if open.is_empty() {
+            return None;
+        }
+        let session = open.remove(0);
+        if open.is_empty() {
+            sessions.remove(&game_id);
+        }
+        Some(EndedSession { started_at: session.started_at, elapsed: session.active_time() })
+    }
+
+    /// Every open session, oldest first.
+    pub fn active(&self) -> Vec<ActiveSession> {
+        let sessions = self.sessions.lock().unwrap();
+        let mut active: Vec<ActiveSession> = sessions
+            .iter()
+            .flat_map(|(game_id, open)| {
+                open.iter().map(move |session| ActiveSession {
+                    game_id: *game_id,
+                    started_at: session.started_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+                    elapsed_minutes: session.active_time().as_secs() / 60,
+                    paused: session.running_since.is_none(),
+                })
+            })
+            .collect();
+        active.sort_by(|a, b| a.started_at.cmp(&b.started_at).then(a.game_id.cmp(&b.game_id)));
+        active
+    }
+
+    /// Stops the clock on the game's oldest open session, keeping the time played so far.
+    pub fn pause(&self, game_id: i64) -> Result<(), String> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let session = sessions.get_mut(&game_id).and_then(|open| open.first_mut()).ok_or_else(|| format!("No active session for game {}", game_id))?;
+        let since = session.running_since.take().ok_or_else(|| format!("Session for game {} is already paused", game_id))?;
+        session.accrued += since.elapsed();
+        Ok(())
+    }
+
+    pub fn resume(&self, game_id: i64) -> Result<(), String> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let session = sessions.get_mut(&game_id).and_then(|open| open.first_mut()).ok_or_else(|| format!("No active session for game {}", game_id))?;
+        if session.running_since.is_some() {
+            return Err(format!("Session for game {} is not paused", game_id));
+        }
+        session.running_since = Some(Instant::now());
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub fn start_game_session(app: AppHandle, game_id: i64, tracker: tauri::State<'_, SessionTracker>) -> Result<(), String> {
+    let conn = get_connection(&app)?;
+    get_game(&conn, game_id).map_err(|e| e.to_string())?;
+    tracker.start(game_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_active_sessions(tracker: tauri::State<'_, SessionTracker>) -> Vec<ActiveSession> {
+    tracker.active()
+}
+
+#[tauri::command]
+pub fn pause_game_session(game_id: i64, tracker: tauri::State<'_, SessionTracker>) -> Result<(), String> {
+    tracker.pause(game_id)
+}
+
+#[tauri::command]
+pub fn resume_game_session(game_id: i64, tracker: tauri::State<'_, SessionTracker>) -> Result<(), String> {
+    tracker.resume(game_id)
+}
+
+/// Ends the oldest open session for the game, counting only unpaused time, and returns the game's new total playtime.
+#[tauri::command]
+pub fn end_game_session(app: AppHandle, game_id: i64, tracker: tauri::State<'_, SessionTracker>) -> Result<i64, String> {
+    finish_game_session(&app, &tracker, game_id)
+}
+
+/// Shared by `end_game_session` and the launcher's process-exit watcher.
+pub fn finish_game_session(app: &AppHandle, tracker: &SessionTracker, game_id: i64) -> Result<i64, String> {
+    let conn = get_connection(app)?;
+    finish_session(&conn, tracker, game_id)
+}
+
+fn finish_session(conn: &Connection, tracker: &SessionTracker, game_id: i64) -> Result<i64, String> {
+    let session = tracker.end(game_id).ok_or_else(|| format!("No active session for game {}", game_id))?;
+    let started_at = session.started_at.to_rfc3339_opts(SecondsFormat::Secs, true);
+    let ended_at = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+    record_play_session(conn, game_id, &started_at, &ended_at, (session.elapsed.as_secs() / 60) as i64).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{create_game, create_platform, create_schema, open_connection};
+    use crate::models::GameRelations;
+
+    // Gives each of the game's open sessions a fixed amount of paused time, oldest first
+    fn set_active_minutes(tracker: &SessionTracker, game_id: i64, minutes: &[u64]) {
+        let mut sessions = tracker.sessions.lock().unwrap();
+        for (session, minutes) in sessions.get_mut(&game_id).unwrap().iter_mut().zip(minutes) {
+            session.accrued = Duration::from_secs(minutes * 60);
+            session.running_since = None;
+        }
+    }
+
+    #[test]
+    fn overlapping_sessions_add_up() {
+        let conn = open_connection(std::path::Path::new(":memory:")).unwrap();
+        create_schema(&conn).unwrap();
+        let platform_id = create_platform(&conn, "PC".to_string(), None, None, None, None, None).unwrap();
+        let game_id = create_game(&conn, "Game".to_string(), platform_id, None, None, None, None, None, None, None, None, false, &GameRelations::default()).unwrap();
+
+        let tracker = SessionTracker::default();
+        tracker.start(game_id);
+        tracker.start(game_id);
+        assert_eq!(tracker.active().len(), 2);
+        set_active_minutes(&tracker, game_id, &[30, 45]);
+
+        finish_session(&conn, &tracker, game_id).unwrap();
+        let total = finish_session(&conn, &tracker, game_id).unwrap();
+        assert!(finish_session(&conn, &tracker, game_id).is_err());
+
+        assert_eq!(total, 75);
+        assert!(tracker.active().is_empty());
+        let ended_before_start: i64 = conn.query_row("SELECT COUNT(*) FROM play_sessions WHERE ended_at < started_at", [], |row| row.get(0)).unwrap();
+        assert_eq!(ended_before_start, 0);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::database::{delete_setting_value, get_connection, get_setting_value, set_setting_value};
+use crate::extensions::ExtensionManager;
+use arcadia_extension_framework::models::ExtensionType;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::RwLock;
+
+const ACTIVE_THEME_KEY: &str = "active_theme";
+
+#[derive(Serialize, Clone)]
+pub struct ThemeAsset {
+    pub path: String,
+    pub content: String,
+}
+
+fn ensure_enabled_theme(manager: &ExtensionManager, extension_id: &str) -> Result<(), String> {
+    let extension = manager
+        .list_extensions()
+        .into_iter()
+        .find(|ext| ext.id == extension_id)
+        .ok_or_else(|| format!("Extension {} not found", extension_id))?;
+    if extension.extension_type != ExtensionType::Theme.to_string() {
+        return Err(format!("Extension {} is not a theme", extension_id));
+    }
+    if !extension.enabled {
+        return Err(format!("Theme {} is disabled", extension_id));
+    }
+    Ok(())
+}
+
+/// Reads the stylesheets at the top level of a theme directory, sorted by file name.
+pub fn read_theme_assets(dir: &Path) -> Result<Vec<ThemeAsset>, String> {
+    let mut assets = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("css")) {
+            let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+            let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+            assets.push(ThemeAsset { path: name, content });
+        }
+    }
+    assets.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(assets)
+}
+
+/// Activates a theme extension, or reverts to the default theme when `extension_id` is null.
+#[tauri::command]
+pub async fn set_active_theme(app: AppHandle, extension_id: Option<String>, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<(), String> {
+    if let Some(extension_id) = &extension_id {
+        let manager = extension_manager.inner().read().await;
+        ensure_enabled_theme(&manager, extension_id)?;
+    }
+    let conn = get_connection(&app)?;
+    let result = match extension_id {
+        Some(extension_id) => set_setting_value(&conn, ACTIVE_THEME_KEY, &extension_id),
+        None => delete_setting_value(&conn, ACTIVE_THEME_KEY),
+    };
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_active_theme(app: AppHandle) -> Result<Option<String>, String> {
+    let conn = get_connection(&app)?;
+    get_setting_value(&conn, ACTIVE_THEME_KEY).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_theme_assets(extension_id: String, extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>) -> Result<Vec<ThemeAsset>, String> {
+    let dir = {
+        let manager = extension_manager.inner().read().await;
+        ensure_enabled_theme(&manager, &extension_id)?;
+        manager.get_extension_dir(&extension_id).map_err(|e| e.to_string())?
+    };
+    read_theme_assets(&dir)
+}
+
+pub const THEME_CHANGED_EVENT: &str = "theme://changed";
+
+#[derive(Serialize, Clone)]
+struct ThemeChanged {
+    extension_id: String,
+    assets: Vec<ThemeAsset>,
+}
+
+/// File watchers for themes being live-reloaded, keyed by extension id. Dropping a watcher stops it.
+#[derive(Default)]
+pub struct ThemeWatchers {
+    watchers: Mutex<HashMap<String, notify::RecommendedWatcher>>,
+}
+
+fn is_stylesheet(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("css"))
+}
+
+fn watch_theme_dir(app: AppHandle, extension_id: String, dir: PathBuf) -> Result<notify::RecommendedWatcher, notify::Error> {
+    use notify::Watcher;
+
+    let watched_dir = dir.clone();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let event = match event {
+            Ok(event) => event,
+            Err(e) => {
+                println!("Theme watcher for {} failed: {}", extension_id, e);
+                return;
+            }
+        };
+        if event.kind.is_access() || !event.paths.iter().any(|path| is_stylesheet(path)) {
+            return;
+        }
+        match read_theme_assets(&watched_dir) {
+            Ok(assets) => {
+                let payload = ThemeChanged { extension_id: extension_id.clone(), assets };
+                if let Err(e) = app.emit(THEME_CHANGED_EVENT, payload) {
+                    println!("Failed to emit {} for {}: {}", THEME_CHANGED_EVENT, extension_id, e);
+                }
+            }
+            Err(e) => println!("Failed to reload theme {}: {}", extension_id, e),
+        }
+    })?;
+    // Only top-level stylesheets are theme assets, so subdirectories aren't watched
+    watcher.watch(&dir, notify::RecursiveMode::NonRecursive)?;
+    Ok(watcher)
+}
+
+/// Development aid for theme authors: emits `theme://changed` with the reloaded assets whenever
+/// a stylesheet in the theme's directory changes, until `stop_watching_theme` is called.
+#[tauri::command]
+pub async fn watch_theme(
+    app: AppHandle,
+    extension_id: String,
+    extension_manager: State<'_, Arc<RwLock<ExtensionManager>>>,
+    theme_watchers: State<'_, ThemeWatchers>,
+) -> Result<(), String> {
+    let dir = {
+        let manager = extension_manager.inner().read().await;
+        ensure_enabled_theme(&manager, &extension_id)?;
+        manager.get_extension_dir(&extension_id).map_err(|e| e.to_string())?
+    };
+    let watcher = watch_theme_dir(app, extension_id.clone(), dir).map_err(|e| format!("Failed to watch theme {}: {}", extension_id, e))?;
+    theme_watchers.watchers.lock().unwrap().insert(extension_id, watcher);
+    Ok(())
+}
+
+/// Stops live-reloading a theme. Returns whether it was being watched.
+#[tauri::command]
+pub fn stop_watching_theme(extension_id: String, theme_watchers: State<'_, ThemeWatchers>) -> bool {
+    theme_watchers.watchers.lock().unwrap().remove(&extension_id).is_some()
+}
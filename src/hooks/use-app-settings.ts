@@ -2,14 +2,14 @@ import { invoke } from '@tauri-apps/api/core';
 import { useCallback } from 'react';
 
 interface UseAppSettingsReturn {
-  getSetting: (key: string) => Promise<string>;
+  getSetting: (key: string) => Promise<string | null>;
   setSetting: (key: string, value: string) => Promise<void>;
 }
 
 export function useAppSettings(): UseAppSettingsReturn {
-  const getSetting = useCallback(async (key: string): Promise<string> => {
+  const getSetting = useCallback(async (key: string): Promise<string | null> => {
     try {
-      return await invoke<string>('get_setting', { key });
+      return await invoke<string | null>('get_setting', { key });
     } catch (error) {
       throw new Error(`Failed to get setting: ${error}`);
     }
@@ -14,6 +14,8 @@ export interface Game {
   working_directory?: string;
   arguments?: string;
   is_favorite: boolean;
+  installed: boolean;
+  image_types: string[];
   playtime_minutes: number;
   last_played?: string;
   created_at: string;
@@ -1,6 +1,6 @@
 import { invoke } from '@tauri-apps/api/core';
 
-export async function getSetting(key: string): Promise<string> {
+export async function getSetting(key: string): Promise<string | null> {
   return await invoke('get_setting', { key });
 }
 
@@ -141,9 +141,11 @@ export async function installExtension(manifestPath: string): Promise<string> {
 /**
  * Uninstall an extension
  * @param extensionId The extension ID to uninstall
+ * @param keepFiles Leave the extension's install directory on disk
+ * @returns Bytes freed on disk
  */
-export async function uninstallExtension(extensionId: string): Promise<void> {
-  return await invoke('uninstall_extension', { extensionId });
+export async function uninstallExtension(extensionId: string, keepFiles = false): Promise<number> {
+  return await invoke('uninstall_extension', { extensionId, keepFiles });
 }
 
 /**